@@ -0,0 +1,136 @@
+// The answer to one part of a day. Solvers return whatever type suits the puzzle,
+// which is converted to an `Answer` such that answers can be compared, stored
+// and submitted alike.
+
+use std::fmt::Display;
+
+use num::{BigInt, BigUint};
+
+use crate::input::{ParseError, ParseResult};
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Answer {
+    Signed(i64),
+    Unsigned(u64),
+    /// An integer too large for 64 bits
+    Big(BigInt),
+    Text(String),
+    /// The part is not solved yet
+    Unimplemented,
+}
+
+/// The answer type of a part that is not solved yet, e.g. `type Answer2 = Unimplemented`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Unimplemented;
+
+impl Answer {
+    pub fn is_implemented(&self) -> bool {
+        *self != Answer::Unimplemented
+    }
+}
+
+impl Display for Answer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Answer::Signed(n) => write!(f, "{n}"),
+            Answer::Unsigned(n) => write!(f, "{n}"),
+            Answer::Big(n) => write!(f, "{n}"),
+            Answer::Text(s) => f.write_str(s),
+            Answer::Unimplemented => f.write_str("pending"),
+        }
+    }
+}
+
+/// What a part may return: anything that converts to an `Answer`, or a
+/// `ParseResult` of it for inputs that only that part cannot handle, which are
+/// reported as malformed like errors of `Solver::parse`
+pub trait IntoAnswer {
+    fn into_answer(self) -> ParseResult<Answer>;
+}
+
+impl<T: Into<Answer>> IntoAnswer for T {
+    fn into_answer(self) -> ParseResult<Answer> {
+        Ok(self.into())
+    }
+}
+
+impl<T: Into<Answer>> IntoAnswer for Result<T, ParseError> {
+    fn into_answer(self) -> ParseResult<Answer> {
+        self.map(Into::into)
+    }
+}
+
+macro_rules! from_integer {
+    ($variant:ident($target:ty): $($t:ty),*) => {
+        $(
+            impl From<$t> for Answer {
+                fn from(n: $t) -> Self {
+                    Answer::$variant(n as $target)
+                }
+            }
+        )*
+    };
+}
+
+from_integer!(Unsigned(u64): u8, u16, u32, u64, usize);
+from_integer!(Signed(i64): i8, i16, i32, i64, isize);
+
+impl From<u128> for Answer {
+    fn from(n: u128) -> Self {
+        u64::try_from(n).map_or_else(|_| Answer::Big(n.into()), Answer::Unsigned)
+    }
+}
+
+impl From<i128> for Answer {
+    fn from(n: i128) -> Self {
+        i64::try_from(n).map_or_else(|_| Answer::Big(n.into()), Answer::Signed)
+    }
+}
+
+impl From<BigInt> for Answer {
+    fn from(n: BigInt) -> Self {
+        Answer::Big(n)
+    }
+}
+
+impl From<BigUint> for Answer {
+    fn from(n: BigUint) -> Self {
+        Answer::Big(n.into())
+    }
+}
+
+impl From<String> for Answer {
+    fn from(s: String) -> Self {
+        Answer::Text(s)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(s: &str) -> Self {
+        Answer::Text(s.to_owned())
+    }
+}
+
+impl From<Unimplemented> for Answer {
+    fn from(_: Unimplemented) -> Self {
+        Answer::Unimplemented
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Answer, Unimplemented};
+
+    #[test]
+    fn test_from() {
+        assert_eq!(Answer::from(288usize), Answer::Unsigned(288));
+        assert_eq!(Answer::from(-3isize), Answer::Signed(-3));
+        assert_eq!(Answer::from(7u128), Answer::Unsigned(7));
+        let big = Answer::from(u128::MAX);
+        assert_eq!(big.to_string(), u128::MAX.to_string());
+        assert!(matches!(big, Answer::Big(_)));
+        assert_eq!(Answer::from("LRL").to_string(), "LRL");
+        assert_eq!(Answer::from(Unimplemented), Answer::Unimplemented);
+        assert!(!Answer::Unimplemented.is_implemented());
+    }
+}
@@ -0,0 +1,156 @@
+// Accepted answers are stored next to the input in the data directory, in a file
+// dayNN.answers with one line per known part, like:
+// 1: 57346
+// 2: 57345
+
+use std::{
+    io::ErrorKind,
+    path::{Path, PathBuf},
+};
+
+use crate::{
+    answer::Answer,
+    error::{Error, Result},
+    Day,
+};
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Answers(pub [Option<String>; 2]);
+
+/// The result of comparing an answer to the stored answer
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Check {
+    Correct,
+    Wrong {
+        expected: String,
+    },
+    Unknown,
+    /// The part is not implemented, so there is nothing to compare
+    Pending,
+}
+
+impl Check {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Check::Correct => "correct",
+            Check::Wrong { .. } => "wrong",
+            Check::Unknown => "unknown",
+            Check::Pending => "pending",
+        }
+    }
+
+    pub fn expected(&self) -> Option<&str> {
+        match self {
+            Check::Wrong { expected } => Some(expected),
+            _ => None,
+        }
+    }
+}
+
+pub fn answers_path(data_dir: &Path, day: Day) -> PathBuf {
+    data_dir.join(format!("day{:02}.answers", day.0))
+}
+
+impl Answers {
+    fn parse(s: &str) -> std::result::Result<Self, String> {
+        let mut answers = Answers::default();
+        for (lineno, line) in s.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() {
+                continue;
+            }
+            let (part, answer) = line
+                .split_once(':')
+                .ok_or_else(|| format!("line {}: expected \"<part>: <answer>\"", lineno + 1))?;
+            let index = match part.trim() {
+                "1" => 0,
+                "2" => 1,
+                p => {
+                    return Err(format!(
+                        "line {}: part must be 1 or 2, not \"{p}\"",
+                        lineno + 1
+                    ))
+                }
+            };
+            answers.0[index] = Some(answer.trim().to_owned());
+        }
+        Ok(answers)
+    }
+
+    fn render(&self) -> String {
+        let mut s = String::new();
+        for (i, answer) in self.0.iter().enumerate() {
+            if let Some(answer) = answer {
+                s.push_str(&format!("{}: {}\n", i + 1, answer));
+            }
+        }
+        s
+    }
+
+    /// Load the answers of a day. If no answers are stored, all answers are unknown.
+    pub fn load(data_dir: &Path, day: Day) -> Result<Self> {
+        Answers::read(answers_path(data_dir, day))
+    }
+
+    /// Read an answers file. If it does not exist, all answers are unknown.
+    pub fn read(path: PathBuf) -> Result<Self> {
+        let s = match std::fs::read_to_string(&path) {
+            Ok(s) => s,
+            Err(e) if e.kind() == ErrorKind::NotFound => return Ok(Answers::default()),
+            Err(e) => return Err(Error::io(path, e)),
+        };
+        Answers::parse(&s).map_err(|reason| Error::BadAnswers { path, reason })
+    }
+
+    /// Store the accepted answer of one part (1 or 2), keeping the other part
+    pub fn record(data_dir: &Path, day: Day, part: u8, answer: &str) -> Result<()> {
+        let mut answers = Answers::load(data_dir, day)?;
+        answers.0[usize::from(part - 1)] = Some(answer.to_owned());
+        answers.write(answers_path(data_dir, day))
+    }
+
+    pub fn write(&self, path: PathBuf) -> Result<()> {
+        std::fs::write(&path, self.render()).map_err(|e| Error::io(path, e))
+    }
+
+    pub fn check(&self, part: u8, actual: &Answer) -> Check {
+        match &self.0[usize::from(part - 1)] {
+            _ if !actual.is_implemented() => Check::Pending,
+            None => Check::Unknown,
+            Some(expected) if *expected == actual.to_string() => Check::Correct,
+            Some(expected) => Check::Wrong {
+                expected: expected.clone(),
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Answers, Check};
+    use crate::answer::Answer;
+
+    #[test]
+    fn test_parse() {
+        let answers = Answers::parse("1: 57346\n\n2:abc \n").unwrap();
+        assert_eq!(
+            answers,
+            Answers([Some("57346".to_owned()), Some("abc".to_owned())])
+        );
+        assert_eq!(Answers::parse(&answers.render()).unwrap(), answers);
+        assert_eq!(answers.check(1, &Answer::Unsigned(57346)), Check::Correct);
+        assert_eq!(
+            answers.check(2, &"abd".into()),
+            Check::Wrong {
+                expected: "abc".to_owned()
+            }
+        );
+        assert_eq!(answers.check(2, &Answer::Unimplemented), Check::Pending);
+        assert_eq!(
+            Answers::default().check(1, &Answer::Signed(1)),
+            Check::Unknown
+        );
+        assert!(Answers::parse("3: 1").is_err());
+        assert!(Answers::parse("57346").is_err());
+    }
+}
@@ -0,0 +1,192 @@
+use std::{
+    hint::black_box,
+    path::Path,
+    time::{Duration, Instant},
+};
+
+use advent2023::{
+    error::{Error, Result},
+    runner::load_input,
+    solver::{get_solver, Parts, Registration, Timings},
+    year::Year,
+    Day,
+};
+
+use crate::get_days;
+
+/// Summary statistics of the time spent in one phase over all iterations
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Stats {
+    min: Duration,
+    median: Duration,
+    mean: Duration,
+    stddev: Duration,
+}
+
+impl Stats {
+    // Panics if v is empty
+    fn new(v: &mut [Duration]) -> Self {
+        v.sort_unstable();
+        let n = v.len();
+        let median = if n % 2 == 1 {
+            v[n / 2]
+        } else {
+            (v[n / 2 - 1] + v[n / 2]) / 2
+        };
+        let mean = v.iter().map(Duration::as_secs_f64).sum::<f64>() / n as f64;
+        let variance = v
+            .iter()
+            .map(|d| (d.as_secs_f64() - mean).powi(2))
+            .sum::<f64>()
+            / n as f64;
+        Stats {
+            min: v[0],
+            median,
+            mean: Duration::from_secs_f64(mean),
+            stddev: Duration::from_secs_f64(variance.sqrt()),
+        }
+    }
+}
+
+// The measured durations of each phase, one element per iteration
+#[derive(Default)]
+struct Samples {
+    read: Vec<Duration>,
+    parse: Vec<Duration>,
+    part1: Vec<Duration>,
+    part2: Vec<Duration>,
+    total: Vec<Duration>,
+}
+
+fn run_once(
+    data_dir: &Path,
+    day: Day,
+    f: &Registration,
+    parts: Parts,
+) -> Result<(Duration, Timings)> {
+    let params = f.default_params();
+    let start = Instant::now();
+    let data = load_input(data_dir, day)?;
+    let read = start.elapsed();
+    let solution = black_box(f.run_parts(black_box(&data), parts, &params))
+        .map_err(|error| Error::BadInput { day, error })?;
+    if let Some(error) = solution.error() {
+        return Err(Error::BadInput {
+            day,
+            error: error.clone(),
+        });
+    }
+    Ok((read, solution.timings))
+}
+
+fn bench_day(
+    data_dir: &Path,
+    day: Day,
+    f: &Registration,
+    parts: Parts,
+    iterations: usize,
+    min_time: Duration,
+) -> Result<Samples> {
+    // Warm up the file system cache and CPU caches for a tenth of the time,
+    // but at least once.
+    let start = Instant::now();
+    loop {
+        run_once(data_dir, day, f, parts)?;
+        if start.elapsed() >= min_time / 10 {
+            break;
+        }
+    }
+
+    let mut samples = Samples::default();
+    let start = Instant::now();
+    while samples.total.len() < iterations || start.elapsed() < min_time {
+        let (read, timings) = run_once(data_dir, day, f, parts)?;
+        samples.read.push(read);
+        samples.parse.push(timings.parse);
+        samples.part1.push(timings.part1);
+        samples.part2.push(timings.part2);
+        samples.total.push(read + timings.total());
+    }
+    Ok(samples)
+}
+
+// Only the phases of the parts that were run are printed
+fn print_samples(day: Day, mut samples: Samples, parts: Parts) {
+    println!("Day {:02} [{} iterations]:", day.0, samples.total.len());
+    println!(
+        "  {:<8}{:>12}{:>12}{:>12}{:>12}",
+        "Phase", "min", "median", "mean", "stddev"
+    );
+    for (name, v, included) in [
+        ("Read", &mut samples.read, true),
+        ("Parse", &mut samples.parse, true),
+        ("Part 1", &mut samples.part1, parts.includes(1)),
+        ("Part 2", &mut samples.part2, parts.includes(2)),
+        ("Total", &mut samples.total, true),
+    ] {
+        if !included {
+            continue;
+        }
+        let stats = Stats::new(v);
+        println!(
+            "  {:<8}{:>12.2?}{:>12.2?}{:>12.2?}{:>12.2?}",
+            name, stats.min, stats.median, stats.mean, stats.stddev
+        );
+    }
+    println!();
+}
+
+/// Run the `parts` of each day repeatedly, until both `iterations` runs have been
+/// done, and at least `min_time` has passed, then print statistics of the time
+/// spent in each phase. `data_dir` is the data directory of the year.
+pub fn bench(
+    data_dir: &Path,
+    year: Year,
+    day_strings: Option<Vec<String>>,
+    all: bool,
+    iterations: usize,
+    min_time: Duration,
+    parts: Parts,
+) -> Result<()> {
+    let days = get_days(day_strings, all, year, Some(data_dir))?;
+    let mut days_and_functions: Vec<_> = days.iter().map(|d| (*d, get_solver(year, *d))).collect();
+
+    // As in `solve`, don't spam "unimplemented" if --all is picked
+    if all {
+        days_and_functions.retain(|x| x.1.is_some());
+        if days_and_functions.is_empty() {
+            eprintln!("No days implemented");
+            return Ok(());
+        }
+    }
+    if !data_dir.is_dir() {
+        return Err(Error::MissingDataDir(data_dir.to_owned()));
+    }
+
+    for (day, f) in days_and_functions.iter() {
+        match f {
+            None => println!("Day {:02}:\n  Unimplemented!\n", day.0),
+            Some(f) => {
+                let samples = bench_day(data_dir, *day, f, parts, iterations.max(1), min_time)?;
+                print_samples(*day, samples, parts)
+            }
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Stats;
+    use std::time::Duration;
+
+    #[test]
+    fn test_stats() {
+        let mut v: Vec<_> = [4, 1, 3, 2].map(Duration::from_secs).to_vec();
+        let stats = Stats::new(&mut v);
+        assert_eq!(stats.min, Duration::from_secs(1));
+        assert_eq!(stats.median, Duration::from_millis(2500));
+        assert_eq!(stats.mean, Duration::from_millis(2500));
+        assert!((stats.stddev.as_secs_f64() - 1.25_f64.sqrt()).abs() < 1e-6);
+    }
+}
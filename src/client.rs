@@ -0,0 +1,377 @@
+use std::{
+    cell::Cell,
+    time::{Duration, Instant},
+};
+
+use reqwest::{
+    blocking::{Client, RequestBuilder},
+    header::{HeaderMap, HeaderValue},
+};
+
+use crate::{
+    config::Config,
+    error::{Error, Result},
+    year::Year,
+    Day,
+};
+
+pub const BASE_URL: &str = "https://adventofcode.com";
+
+// Part of the response when downloading an input without a valid session
+const LOGGED_OUT: &str = "Please log in";
+
+/// How the client behaves towards the server
+#[derive(Debug, Clone, PartialEq)]
+pub struct ClientOptions {
+    /// Contact information of the user, e.g. an email address, sent in the User-Agent
+    pub contact: Option<String>,
+    /// Minimum time between the start of two requests
+    pub min_interval: Duration,
+    /// Number of times a request is retried after a transient failure
+    pub retries: u32,
+    /// Time to wait before the first retry. It doubles with each retry.
+    pub retry_delay: Duration,
+    /// Maximum time for a request, from connecting until the body is read
+    pub timeout: Duration,
+}
+
+impl Default for ClientOptions {
+    fn default() -> Self {
+        ClientOptions {
+            contact: None,
+            min_interval: Duration::from_secs(1),
+            retries: 3,
+            retry_delay: Duration::from_secs(1),
+            timeout: Duration::from_secs(30),
+        }
+    }
+}
+
+impl ClientOptions {
+    /// The User-Agent identifying this tool and its user, as asked for by Advent of Code
+    pub fn user_agent(&self) -> String {
+        let name = concat!(env!("CARGO_PKG_NAME"), "/", env!("CARGO_PKG_VERSION"));
+        match &self.contact {
+            Some(contact) => format!("{name} (contact: {contact})"),
+            None => name.to_owned(),
+        }
+    }
+
+    /// A warning for the user if the options lack what Advent of Code asks for
+    pub fn warning(&self) -> Option<&'static str> {
+        self.contact.is_none().then_some(
+            "No contact information configured. Please set `contact` in the configuration file",
+        )
+    }
+}
+
+type RetryHook = Box<dyn Fn(&str, Duration)>;
+
+/// An HTTP client authenticated with the user's session token
+pub struct AocClient {
+    client: Client,
+    base_url: String,
+    options: ClientOptions,
+    // The start of the previous request, to throttle requests
+    last_request: Cell<Option<Instant>>,
+    // Called with the URL and the delay before each retry
+    on_retry: Option<RetryHook>,
+}
+
+impl AocClient {
+    /// Create a client using the session token and options of the configuration.
+    /// See `ClientOptions::warning` for what the user should be told about them.
+    pub fn from_config(config: &Config) -> Result<Self> {
+        let session = config.session()?;
+        Self::new(&session.token, BASE_URL, config.client.clone())
+    }
+
+    pub fn new(session: &str, base_url: &str, options: ClientOptions) -> Result<Self> {
+        let mut headers = HeaderMap::default();
+        let cookie =
+            HeaderValue::from_str(format!("session={}", session).as_str()).map_err(|_| {
+                Error::MissingSession("The session token contains invalid characters".to_owned())
+            })?;
+        headers.insert("Cookie", cookie);
+        let client = Client::builder()
+            .default_headers(headers)
+            .user_agent(options.user_agent())
+            .timeout(options.timeout)
+            .build()
+            .map_err(|source| Error::Http {
+                url: base_url.to_owned(),
+                attempts: 0,
+                source,
+            })?;
+        Ok(AocClient {
+            client,
+            base_url: base_url.trim_end_matches('/').to_owned(),
+            options,
+            last_request: Cell::new(None),
+            on_retry: None,
+        })
+    }
+
+    /// Call `f` with the URL and the delay before retrying a failed request,
+    /// e.g. to tell the user why the request takes longer
+    pub fn on_retry(mut self, f: impl Fn(&str, Duration) + 'static) -> Self {
+        self.on_retry = Some(Box::new(f));
+        self
+    }
+
+    // Wait until at least `min_interval` has passed since the previous request
+    fn throttle(&self) {
+        if let Some(last) = self.last_request.get() {
+            let elapsed = last.elapsed();
+            if elapsed < self.options.min_interval {
+                std::thread::sleep(self.options.min_interval - elapsed);
+            }
+        }
+        self.last_request.set(Some(Instant::now()));
+    }
+
+    // Send the request, and return the status code and body of the response.
+    // Transient failures are retried with exponential backoff. Requests that
+    // are not `idempotent` are only retried if they could not be sent at all.
+    fn send(&self, url: &str, request: RequestBuilder, idempotent: bool) -> Result<(u16, String)> {
+        let mut delay = self.options.retry_delay;
+        let mut attempt = 0;
+        loop {
+            attempt += 1;
+            let retries_left = attempt <= self.options.retries;
+            self.throttle();
+            // Requests without a streaming body can always be cloned
+            let result = request.try_clone().unwrap().send().and_then(|resp| {
+                let status = resp.status().as_u16();
+                Ok((status, resp.text()?))
+            });
+            let retry = match &result {
+                Ok((status, _)) => idempotent && (*status == 429 || *status >= 500),
+                Err(e) => e.is_connect() || (idempotent && e.is_timeout()),
+            };
+            if !(retry && retries_left) {
+                return result.map_err(|source| Error::Http {
+                    url: url.to_owned(),
+                    attempts: attempt,
+                    source,
+                });
+            }
+            if let Some(on_retry) = &self.on_retry {
+                on_retry(url, delay);
+            }
+            std::thread::sleep(delay);
+            delay *= 2;
+        }
+    }
+
+    /// Download the input of a day. If the day is not released yet, return Error::Unreleased
+    pub fn download_input(&self, year: Year, day: Day) -> Result<String> {
+        self.get_day_page(day, &format!("/{}/day/{}/input", year, day.0))
+    }
+
+    /// Download the HTML page with the puzzle description of the day
+    pub fn download_puzzle(&self, year: Year, day: Day) -> Result<String> {
+        self.get_day_page(day, &format!("/{}/day/{}", year, day.0))
+    }
+
+    fn get_day_page(&self, day: Day, path: &str) -> Result<String> {
+        let url = format!("{}{}", self.base_url, path);
+        let (status, text) = self.send(&url, self.client.get(&url), true)?;
+        if !(200..300).contains(&status) {
+            if text.contains("Please don't repeatedly request this endpoint before it unlocks") {
+                return Err(Error::Unreleased(day));
+            } else if text.contains(LOGGED_OUT) {
+                return Err(Error::ExpiredSession);
+            } else {
+                return Err(Error::BadResponse {
+                    url,
+                    status,
+                    body: text,
+                });
+            }
+        }
+        Ok(text)
+    }
+
+    /// Post an answer to one part (1 or 2) of a day, and return the HTML of the response
+    pub fn submit(&self, year: Year, day: Day, part: u8, answer: &str) -> Result<String> {
+        let url = format!("{}/{}/day/{}/answer", self.base_url, year, day.0);
+        let level = part.to_string();
+        let request = self
+            .client
+            .post(&url)
+            .form(&[("level", level.as_str()), ("answer", answer)]);
+        let (status, text) = self.send(&url, request, false)?;
+        if text.contains(LOGGED_OUT) {
+            return Err(Error::ExpiredSession);
+        }
+        if !(200..300).contains(&status) {
+            return Err(Error::BadResponse {
+                url,
+                status,
+                body: text,
+            });
+        }
+        Ok(text)
+    }
+
+    /// Get the name of the logged in user, or None if the session is not valid
+    pub fn user(&self, year: Year) -> Result<Option<String>> {
+        // A small page, which shows the user in its header
+        let url = format!("{}/{}/about", self.base_url, year);
+        let (status, text) = self.send(&url, self.client.get(&url), true)?;
+        if !(200..300).contains(&status) {
+            return Err(Error::BadResponse {
+                url,
+                status,
+                body: text,
+            });
+        }
+        Ok(parse_user(&text))
+    }
+}
+
+// Get the user name from the page header, e.g. `<div class="user">Name <span ...`
+fn parse_user(html: &str) -> Option<String> {
+    let pattern = "<div class=\"user\">";
+    let start = html.find(pattern)? + pattern.len();
+    let len = html[start..].find('<').unwrap_or(html.len() - start);
+    let name = html[start..start + len].trim();
+    (!name.is_empty()).then(|| name.to_owned())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{parse_user, AocClient, ClientOptions};
+    use crate::{error::Error, testserver::TestServer, year::Year, Day};
+    use std::{
+        cell::RefCell,
+        net::TcpListener,
+        rc::Rc,
+        time::{Duration, Instant},
+    };
+
+    fn options() -> ClientOptions {
+        ClientOptions {
+            contact: Some("me@example.com".to_owned()),
+            min_interval: Duration::ZERO,
+            retries: 2,
+            retry_delay: Duration::from_millis(1),
+            timeout: Duration::from_secs(5),
+        }
+    }
+
+    #[test]
+    fn test_retry() {
+        let server = TestServer::new(vec![
+            (500, "Oops".to_owned()),
+            (503, "Busy".to_owned()),
+            (200, "1abc2".to_owned()),
+        ]);
+        let retries = Rc::new(RefCell::new(Vec::new()));
+        let notified = Rc::clone(&retries);
+        let client = AocClient::new("abc", &server.url, options())
+            .unwrap()
+            .on_retry(move |url, delay| notified.borrow_mut().push((url.to_owned(), delay)));
+        assert_eq!(client.download_input(Year(2023), Day(1)).unwrap(), "1abc2");
+        let url = format!("{}/2023/day/1/input", server.url);
+        let requests = server.join();
+        assert_eq!(requests.len(), 3);
+        assert_eq!(
+            *retries.borrow(),
+            [
+                (url.clone(), Duration::from_millis(1)),
+                (url, Duration::from_millis(2))
+            ]
+        );
+        assert!(requests[0].starts_with("GET /2023/day/1/input "));
+        assert!(requests[0].contains(&format!(
+            "user-agent: advent2023/{} (contact: me@example.com)",
+            env!("CARGO_PKG_VERSION")
+        )));
+
+        // Only the initial request and 2 retries are made
+        let server = TestServer::new(vec![(500, "Oops".to_owned()); 3]);
+        let client = AocClient::new("abc", &server.url, options()).unwrap();
+        match client.download_input(Year(2023), Day(1)) {
+            Err(Error::BadResponse { status: 500, .. }) => (),
+            _ => panic!("Expected an error response"),
+        }
+        assert_eq!(server.join().len(), 3);
+
+        // Answers are not submitted twice
+        let server = TestServer::new(vec![(500, "Oops".to_owned())]);
+        let client = AocClient::new("abc", &server.url, options()).unwrap();
+        assert!(client.submit(Year(2023), Day(1), 1, "5").is_err());
+        assert_eq!(server.join().len(), 1);
+    }
+
+    #[test]
+    fn test_user() {
+        let header = |user: &str| {
+            format!("<header><div><h1 class=\"title-global\"><a href=\"/\">Advent of Code</a></h1>{user}</div></header>")
+        };
+        assert_eq!(
+            parse_user(&header(
+                "<div class=\"user\">Jane Doe <span class=\"star-count\">22*</span></div>"
+            )),
+            Some("Jane Doe".to_owned())
+        );
+        assert_eq!(
+            parse_user(&header("<div class=\"user\">(anonymous user #123)</div>")),
+            Some("(anonymous user #123)".to_owned())
+        );
+        assert_eq!(
+            parse_user(&header("<a href=\"/2023/auth/login\">[Log In]</a>")),
+            None
+        );
+
+        let server = TestServer::new(vec![(
+            400,
+            "Puzzle inputs differ by user.  Please log in to get your puzzle input.\n".to_owned(),
+        )]);
+        let client = AocClient::new("abc", &server.url, options()).unwrap();
+        assert!(matches!(
+            client.download_input(Year(2023), Day(1)),
+            Err(Error::ExpiredSession)
+        ));
+        server.join();
+    }
+
+    #[test]
+    fn test_throttle() {
+        let server = TestServer::new(vec![(200, String::new()); 3]);
+        let options = ClientOptions {
+            min_interval: Duration::from_millis(50),
+            ..options()
+        };
+        let client = AocClient::new("abc", &server.url, options).unwrap();
+        let start = Instant::now();
+        for _ in 0..3 {
+            client.download_puzzle(Year(2023), Day(1)).unwrap();
+        }
+        assert!(start.elapsed() >= Duration::from_millis(100));
+        server.join();
+    }
+
+    #[test]
+    fn test_timeout() {
+        // Connections are accepted by the OS, but never answered
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let options = ClientOptions {
+            retries: 1,
+            timeout: Duration::from_millis(50),
+            ..options()
+        };
+        let client = AocClient::new("abc", &url, options).unwrap();
+        match client.download_input(Year(2023), Day(1)) {
+            Err(Error::Http {
+                attempts: 2,
+                source,
+                ..
+            }) => assert!(source.is_timeout()),
+            _ => panic!("Expected a timeout"),
+        }
+    }
+}
@@ -0,0 +1,256 @@
+// The configuration file holds settings that rarely change between runs, with
+// one `key = value` setting per line, and comments starting with `#`:
+// contact = me@example.com
+// min_interval = 1.5
+// retries = 3
+// timeout = 30
+// session_file = /path/to/session
+//
+// It is read from the path in ADVENTOFCODE_CONFIG if set, else from
+// $XDG_CONFIG_HOME/advent2023/config or ~/.config/advent2023/config.
+// A missing file is the same as an empty one.
+//
+// The session token is taken from the first of:
+// * The environmental variable ADVENTOFCODE_SESSION
+// * The `session` setting of the configuration file
+// * The token file, set by `session_file`, by default `session` next to the
+//   configuration file
+// Files holding the token must not be accessible by other users.
+
+use std::{
+    io::ErrorKind,
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use crate::{
+    client::ClientOptions,
+    error::{Error, Result},
+};
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Config {
+    pub client: ClientOptions,
+    session: Option<String>,
+    session_file: Option<PathBuf>,
+    // The file the configuration was loaded from, if any
+    path: Option<PathBuf>,
+}
+
+/// The session token, and a description of where it was found
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Session {
+    pub token: String,
+    pub source: String,
+}
+
+fn var(name: &str) -> Option<std::ffi::OsString> {
+    std::env::var_os(name).filter(|s| !s.is_empty())
+}
+
+// The directory of the default configuration and token files
+fn config_dir() -> Option<PathBuf> {
+    let dir = match var("XDG_CONFIG_HOME") {
+        Some(dir) => PathBuf::from(dir),
+        None => PathBuf::from(var("HOME")?).join(".config"),
+    };
+    Some(dir.join("advent2023"))
+}
+
+/// The path of the configuration file, or None if no home directory is known
+pub fn config_path() -> Option<PathBuf> {
+    match var("ADVENTOFCODE_CONFIG") {
+        Some(path) => Some(path.into()),
+        None => Some(config_dir()?.join("config")),
+    }
+}
+
+// Refuse files holding the session token if other users can access them
+#[cfg(unix)]
+fn check_private(path: &Path) -> Result<()> {
+    use std::os::unix::fs::PermissionsExt;
+    let metadata = std::fs::metadata(path).map_err(|e| Error::io(path, e))?;
+    if metadata.permissions().mode() & 0o077 != 0 {
+        return Err(Error::InsecureSession(path.to_owned()));
+    }
+    Ok(())
+}
+
+#[cfg(not(unix))]
+fn check_private(_path: &Path) -> Result<()> {
+    Ok(())
+}
+
+/// Parse a non-negative number of seconds, like "1.5"
+pub fn parse_seconds(s: &str) -> std::result::Result<Duration, String> {
+    s.parse::<f64>()
+        .ok()
+        .and_then(|secs| Duration::try_from_secs_f64(secs).ok())
+        .ok_or_else(|| format!("\"{s}\" is not a non-negative number of seconds"))
+}
+
+// The token may be copied with the name of the cookie
+fn clean_token(s: &str) -> String {
+    let s = s.trim();
+    s.strip_prefix("session=").unwrap_or(s).to_owned()
+}
+
+impl Config {
+    fn parse(s: &str) -> std::result::Result<Self, String> {
+        let mut config = Config::default();
+        for (lineno, line) in s.lines().enumerate() {
+            let line = line.split_once('#').map_or(line, |(line, _)| line).trim();
+            if line.is_empty() {
+                continue;
+            }
+            let error = |reason: String| format!("line {}: {reason}", lineno + 1);
+            let (key, value) = line
+                .split_once('=')
+                .ok_or_else(|| error("expected \"<key> = <value>\"".to_owned()))?;
+            let value = value.trim();
+            let seconds = |value| -> std::result::Result<Duration, String> {
+                parse_seconds(value).map_err(error)
+            };
+            let client = &mut config.client;
+            match key.trim() {
+                "contact" => client.contact = Some(value.to_owned()),
+                "session" => config.session = Some(clean_token(value)),
+                "session_file" => config.session_file = Some(value.into()),
+                "min_interval" => client.min_interval = seconds(value)?,
+                "retries" => {
+                    client.retries = value
+                        .parse()
+                        .map_err(|_| error(format!("\"{value}\" is not a number of retries")))?
+                }
+                "retry_delay" => client.retry_delay = seconds(value)?,
+                "timeout" => client.timeout = seconds(value)?,
+                key => return Err(error(format!("unknown setting \"{key}\""))),
+            }
+        }
+        Ok(config)
+    }
+
+    /// Load the configuration file, using the defaults if there is none
+    pub fn load() -> Result<Self> {
+        let Some(path) = config_path() else {
+            return Ok(Config::default());
+        };
+        match std::fs::read_to_string(&path) {
+            Ok(s) => match Config::parse(&s) {
+                Ok(config) => Ok(Config {
+                    path: Some(path),
+                    ..config
+                }),
+                Err(reason) => Err(Error::BadConfig { path, reason }),
+            },
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(Config::default()),
+            Err(e) => Err(Error::io(path, e)),
+        }
+    }
+
+    /// Find the session token, as described at the top of this file
+    pub fn session(&self) -> Result<Session> {
+        let env = std::env::var("ADVENTOFCODE_SESSION").ok();
+        self.find_session(env.filter(|s| !s.is_empty()))
+    }
+
+    fn find_session(&self, env: Option<String>) -> Result<Session> {
+        if let Some(token) = env {
+            return Ok(Session {
+                token: clean_token(&token),
+                source: "environmental variable ADVENTOFCODE_SESSION".to_owned(),
+            });
+        }
+        if let Some(token) = &self.session {
+            let source = match &self.path {
+                Some(path) => {
+                    check_private(path)?;
+                    format!("configuration file {path:?}")
+                }
+                None => "configuration".to_owned(),
+            };
+            return Ok(Session {
+                token: token.clone(),
+                source,
+            });
+        }
+        let Some(path) = self
+            .session_file
+            .clone()
+            .or_else(|| Some(config_dir()?.join("session")))
+        else {
+            return Err(Error::MissingSession(
+                "No session token found. Set the environmental variable ADVENTOFCODE_SESSION"
+                    .to_owned(),
+            ));
+        };
+        match std::fs::read_to_string(&path) {
+            Ok(s) => {
+                check_private(&path)?;
+                Ok(Session {
+                    token: clean_token(&s),
+                    source: format!("token file {path:?}"),
+                })
+            }
+            Err(e) if e.kind() == ErrorKind::NotFound => Err(Error::MissingSession(format!(
+                "No session token found. Set the environmental variable ADVENTOFCODE_SESSION, \
+                 or write the token to {path:?}"
+            ))),
+            Err(e) => Err(Error::io(path, e)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Config;
+    use crate::error::Error;
+    use std::time::Duration;
+
+    #[test]
+    fn test_parse() {
+        let config = Config::parse(
+            "# Settings\ncontact = me@example.com \n\nmin_interval=0.5\nretries = 5 # More\n",
+        )
+        .unwrap();
+        assert_eq!(config.client.contact.as_deref(), Some("me@example.com"));
+        assert_eq!(config.client.min_interval, Duration::from_millis(500));
+        assert_eq!(config.client.retries, 5);
+        assert_eq!(config.client.timeout, Config::default().client.timeout);
+        assert!(Config::parse("retries = -1").is_err());
+        assert!(Config::parse("colour = blue").is_err());
+        assert!(Config::parse("contact").is_err());
+    }
+
+    #[test]
+    fn test_session() {
+        let config = Config::parse("session = session=abc\n").unwrap();
+        assert_eq!(config.find_session(None).unwrap().token, "abc");
+        let session = config.find_session(Some("def".to_owned())).unwrap();
+        assert_eq!(session.token, "def");
+
+        let dir = std::env::temp_dir().join(format!("advent2023-session-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("session");
+        std::fs::write(&path, "ghi\n").unwrap();
+        let config = Config::parse(&format!("session_file = {}", path.display())).unwrap();
+        #[cfg(unix)]
+        use std::os::unix::fs::PermissionsExt;
+        #[cfg(unix)]
+        std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o600)).unwrap();
+        let private = config.find_session(None);
+        #[cfg(unix)]
+        let public = {
+            std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o644)).unwrap();
+            config.find_session(None)
+        };
+        let missing = Config::parse(&format!("session_file = {}", dir.join("x").display()))
+            .unwrap()
+            .find_session(None);
+        std::fs::remove_dir_all(&dir).unwrap();
+        assert_eq!(private.unwrap().token, "ghi");
+        #[cfg(unix)]
+        assert!(matches!(public, Err(Error::InsecureSession(_))));
+        assert!(matches!(missing, Err(Error::MissingSession(_))));
+    }
+}
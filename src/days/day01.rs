@@ -1,8 +1,16 @@
+use crate::{
+    input::{lines, Line, ParseResult},
+    solver::{Registration, Solver},
+};
+
 const NUMBERS: &[&str; 10] = &[
     "zero", "one", "two", "three", "four", "five", "six", "seven", "eight", "nine",
 ];
 
-fn parse_line(bytes: &[u8]) -> (u8, u8) {
+// The calibration values of a line. Part 1 only counts numeric digits, so a line
+// with only spelled out digits is an error in part 1 alone.
+fn parse_line(line: &Line) -> ParseResult<(ParseResult<u8>, u8)> {
+    let bytes = line.text.as_bytes();
     let mut first_p1 = 0xff;
     let mut last_p1 = 0xff;
     let mut first_p2 = 0xff;
@@ -35,23 +43,61 @@ fn parse_line(bytes: &[u8]) -> (u8, u8) {
         }
         last_p2 = digit;
     }
-    let p1 = 10 * first_p1 + last_p1;
-    let p2 = 10 * first_p2 + last_p2;
-    (p1, p2)
+    if first_p2 == 0xff {
+        return Err(line.error("no digit in line"));
+    }
+    let p1 = if first_p1 == 0xff {
+        Err(line.error("no numeric digit in line"))
+    } else {
+        Ok(10 * first_p1 + last_p1)
+    };
+    Ok((p1, 10 * first_p2 + last_p2))
 }
 
-pub fn solve(s: &str) -> (usize, usize) {
-    s.lines()
-        .map(str::trim)
-        .filter(|s| !s.is_empty())
-        .fold((0, 0), |(p1, p2), line| {
-            let (p1_new, p2_new) = parse_line(line.as_bytes());
-            (p1 + usize::from(p1_new), p2 + usize::from(p2_new))
+pub struct Calibration {
+    // The first line without a numeric digit is only an error if part 1 is solved
+    p1: ParseResult<Vec<u8>>,
+    p2: Vec<u8>,
+}
+
+pub struct Day01;
+
+impl Solver for Day01 {
+    type Parsed = Calibration;
+    type Answer1 = ParseResult<usize>;
+    type Answer2 = usize;
+
+    const YEAR: u16 = 2023;
+    const DAY: u8 = 1;
+    const TITLE: &'static str = "Trebuchet?!";
+
+    fn parse(s: &str) -> ParseResult<Self::Parsed> {
+        let values = lines(s)
+            .map(|line| parse_line(&line))
+            .collect::<ParseResult<Vec<_>>>()?;
+        let (p1, p2): (Vec<_>, _) = values.into_iter().unzip();
+        Ok(Calibration {
+            p1: p1.into_iter().collect(),
+            p2,
         })
+    }
+
+    fn part1(calibration: &Self::Parsed) -> ParseResult<usize> {
+        let p1 = calibration.p1.as_ref().map_err(Clone::clone)?;
+        Ok(p1.iter().map(|&v| usize::from(v)).sum())
+    }
+
+    fn part2(calibration: &Self::Parsed) -> usize {
+        calibration.p2.iter().map(|&v| usize::from(v)).sum()
+    }
 }
 
+inventory::submit! { Registration::new::<Day01>() }
+
 #[cfg(test)]
 mod tests {
+    use crate::{input::ParseError, solver::Solver};
+
     static TEST_STR_1: &str = "1abc2
     pqr3stu8vwx
     a1b2c3d4e5f
@@ -67,7 +113,20 @@ mod tests {
 
     #[test]
     fn test() {
-        assert_eq!(super::solve(TEST_STR_1), (142, 142));
-        assert_eq!(super::solve(TEST_STR_2), (242, 229));
+        assert_eq!(super::Day01::solve(TEST_STR_1).unwrap(), (Ok(142), 142));
+        assert_eq!(super::Day01::solve(TEST_STR_2).unwrap(), (Ok(242), 229));
+    }
+
+    #[test]
+    fn test_missing_digit() {
+        // Spelled out digits only count in part 2
+        assert_eq!(
+            super::Day01::solve("1abc2\nfourtwo\n").unwrap(),
+            (Err(ParseError::new(2, "no numeric digit in line")), 54)
+        );
+        assert_eq!(
+            super::Day01::solve("1abc2\nabc\n").err(),
+            Some(ParseError::new(2, "no digit in line"))
+        );
     }
 }
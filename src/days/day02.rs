@@ -1,5 +1,10 @@
+use crate::{
+    input::{lines, Line, ParseResult},
+    params::{Param, Params},
+    solver::{Registration, Solver},
+};
+
 // TODO: Redo parsing, maybe with some kind of crate?
-// solve_parsed can be more elegant
 
 struct Draw([u32; 3]);
 
@@ -9,10 +14,10 @@ impl Draw {
     }
 }
 
-struct Game(Vec<Draw>);
+pub struct Game(Vec<Draw>);
 
 impl Game {
-    fn is_possible(&self, max: Draw) -> bool {
+    fn is_possible(&self, max: &Draw) -> bool {
         self.0
             .iter()
             .all(|draw| draw.0.iter().zip(max.0.iter()).all(|(a, b)| a <= b))
@@ -30,59 +35,100 @@ impl Game {
     }
 }
 
-const MAX_DRAW: Draw = Draw([12, 13, 14]);
+/// The games, and the most cubes of each color in the bag for part 1
+pub struct Games {
+    games: Vec<Game>,
+    max_draw: Draw,
+}
 
 // TODO: Draw twice in one round - validate
-fn parse_draw(s: &str) -> Draw {
+fn parse_draw(line: &Line, s: &str) -> ParseResult<Draw> {
     let mut result = Draw([0; 3]);
     for cube in s.trim().split(", ") {
-        let (a, b) = cube.trim().split_once(' ').unwrap();
-        let n = a.parse::<u32>().unwrap();
+        let (a, b) = line.split_once(cube.trim(), " ")?;
+        let n = line.parse::<u32>(a, "count")?;
         match b {
             "red" => result.0[0] = n,
             "green" => result.0[1] = n,
             "blue" => result.0[2] = n,
-            _ => unreachable!(),
+            _ => return Err(line.error_at(b, format!("invalid color '{b}'"))),
         };
     }
-    result
+    Ok(result)
 }
 
 // TODO: Compile time regex
-fn parse_game(s: &str) -> Game {
-    let (_, rest) = s.split_once(": ").unwrap();
-    Game(rest.split("; ").map(parse_draw).collect())
+fn parse_game(line: Line) -> ParseResult<Game> {
+    let (_, rest) = line.split_once(line.text, ": ")?;
+    let draws = rest.split("; ").map(|draw| parse_draw(&line, draw));
+    Ok(Game(draws.collect::<ParseResult<_>>()?))
 }
 
-fn parse(s: &str) -> Vec<Game> {
-    s.lines()
-        .map(str::trim)
-        .filter(|s| !s.is_empty())
-        .map(parse_game)
-        .collect()
-}
+pub struct Day02;
 
-// TODO Slice of as ref slice?
-fn solve_parsed(v: &[Game]) -> (usize, usize) {
-    v.iter().enumerate().fold((0, 0), |(p1, p2), (i, game)| {
-        (
-            p1 + (i + 1) * (game.is_possible(MAX_DRAW) as usize),
-            p2 + game
-                .max_drawn()
-                .0
-                .iter()
-                .map(|i| *i as usize)
-                .product::<usize>(),
-        )
-    })
-}
+impl Solver for Day02 {
+    type Parsed = Games;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    const YEAR: u16 = 2023;
+    const DAY: u8 = 2;
+    const TITLE: &'static str = "Cube Conundrum";
+    const PARAMS: &'static [Param] = &[
+        Param::new::<u32>("red", "12", "The number of red cubes in the bag in part 1"),
+        Param::new::<u32>(
+            "green",
+            "13",
+            "The number of green cubes in the bag in part 1",
+        ),
+        Param::new::<u32>(
+            "blue",
+            "14",
+            "The number of blue cubes in the bag in part 1",
+        ),
+    ];
+
+    fn parse(s: &str) -> ParseResult<Self::Parsed> {
+        Self::parse_with(s, &Params::defaults(Self::PARAMS))
+    }
+
+    fn parse_with(s: &str, params: &Params) -> ParseResult<Self::Parsed> {
+        Ok(Games {
+            games: lines(s).map(parse_game).collect::<ParseResult<_>>()?,
+            max_draw: Draw(["red", "green", "blue"].map(|color| params.get(color))),
+        })
+    }
+
+    fn part1(v: &Self::Parsed) -> usize {
+        v.games
+            .iter()
+            .enumerate()
+            .filter(|(_, game)| game.is_possible(&v.max_draw))
+            .map(|(i, _)| i + 1)
+            .sum()
+    }
 
-pub fn solve(s: &str) -> (usize, usize) {
-    solve_parsed(&parse(s))
+    fn part2(v: &Self::Parsed) -> usize {
+        v.games
+            .iter()
+            .map(|game| {
+                game.max_drawn()
+                    .0
+                    .iter()
+                    .map(|i| *i as usize)
+                    .product::<usize>()
+            })
+            .sum()
+    }
 }
 
+inventory::submit! { Registration::new::<Day02>() }
+
 #[cfg(test)]
 mod tests {
+    use super::Day02;
+    use crate::{params::Params, solver::Solver};
+
     static TEST_STR: &str = "Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green
     Game 2: 1 blue, 2 green; 3 green, 4 blue, 1 red; 1 green, 1 blue
     Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red
@@ -91,6 +137,21 @@ mod tests {
 
     #[test]
     fn test() {
-        assert_eq!(super::solve(TEST_STR), (8, 2286));
+        assert_eq!(Day02::solve(TEST_STR).unwrap(), (8, 2286));
+    }
+
+    #[test]
+    fn test_params() {
+        let solve = |red: &str, green: &str, blue: &str| {
+            let assignments = [("red", red), ("green", green), ("blue", blue)]
+                .map(|(name, value)| (name.to_owned(), value.to_owned()));
+            let params = Params::new(Day02::PARAMS, &assignments).unwrap();
+            let games = Day02::parse_with(TEST_STR, &params).unwrap();
+            (Day02::part1(&games), Day02::part2(&games))
+        };
+        // Game 3 needs 20 red cubes, and game 4 needs 15 blue ones
+        assert_eq!(solve("20", "13", "15"), (15, 2286));
+        // Only games 1 and 2 fit in a bag of 4 red, 3 green and 6 blue cubes
+        assert_eq!(solve("4", "3", "6"), (3, 2286));
     }
 }
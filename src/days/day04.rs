@@ -1,34 +1,76 @@
-pub fn solve(s: &str) -> (usize, usize) {
-    const LEN: usize = 10;
-    let mut copies = [1_u32; LEN];
-    let mut left_side = [0u8; LEN];
-    let mut p1 = 0;
-    let mut p2 = 0;
-    for line in s.lines().map(str::trim).filter(|s| !s.is_empty()) {
-        let (left, right) = line.split_once(':').unwrap().1.split_once('|').unwrap();
-        for (i, n) in left.split_ascii_whitespace().enumerate() {
-            left_side[i] = n.parse().unwrap()
-        }
-        let n_overlap = right
-            .split_ascii_whitespace()
-            .filter(|n| left_side.contains(&n.parse::<u8>().unwrap()))
-            .count();
-        if n_overlap > 0 {
-            p1 += 2_usize.pow((n_overlap - 1) as u32);
-        }
-        let copy = copies[0];
-        copies.rotate_left(1);
-        copies[LEN - 1] = 1;
-        for i in copies.iter_mut().take(n_overlap) {
-            *i += copy
+use crate::{
+    input::{lines, ParseResult},
+    solver::{Registration, Solver},
+};
+
+const LEN: usize = 10;
+
+pub struct Day04;
+
+impl Solver for Day04 {
+    type Parsed = Vec<usize>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    const YEAR: u16 = 2023;
+    const DAY: u8 = 4;
+    const TITLE: &'static str = "Scratchcards";
+
+    // Number of winning numbers on each card
+    fn parse(s: &str) -> ParseResult<Self::Parsed> {
+        let mut left_side = [0u8; LEN];
+        lines(s)
+            .map(|line| {
+                let (_, numbers) = line.split_once(line.text, ":")?;
+                let (left, right) = line.split_once(numbers, "|")?;
+                let mut n_left = 0;
+                for n in left.split_ascii_whitespace() {
+                    if n_left == LEN {
+                        return Err(line.error_at(n, format!("more than {LEN} winning numbers")));
+                    }
+                    left_side[n_left] = line.parse(n, "number")?;
+                    n_left += 1;
+                }
+                let mut n_overlap = 0;
+                for n in right.split_ascii_whitespace() {
+                    if left_side[..n_left].contains(&line.parse(n, "number")?) {
+                        n_overlap += 1
+                    }
+                }
+                Ok(n_overlap)
+            })
+            .collect()
+    }
+
+    fn part1(v: &Self::Parsed) -> usize {
+        v.iter()
+            .filter(|&&n_overlap| n_overlap > 0)
+            .map(|&n_overlap| 2_usize.pow((n_overlap - 1) as u32))
+            .sum()
+    }
+
+    fn part2(v: &Self::Parsed) -> usize {
+        let mut copies = [1_u32; LEN];
+        let mut p2 = 0;
+        for &n_overlap in v.iter() {
+            let copy = copies[0];
+            copies.rotate_left(1);
+            copies[LEN - 1] = 1;
+            for i in copies.iter_mut().take(n_overlap) {
+                *i += copy
+            }
+            p2 += copy;
         }
-        p2 += copy;
+        p2 as usize
     }
-    (p1, p2 as usize)
 }
 
+inventory::submit! { Registration::new::<Day04>() }
+
 #[cfg(test)]
 mod tests {
+    use crate::solver::Solver;
+
     static TEST_STR: &str = "Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53
     Card 2: 13 32 20 16 61 | 61 30 68 82 17 32 24 19
     Card 3:  1 21 53 59 44 | 69 82 63 72 16 21 14  1
@@ -39,6 +81,6 @@ mod tests {
 
     #[test]
     fn test() {
-        assert_eq!(super::solve(TEST_STR), (13, 30));
+        assert_eq!(super::Day04::solve(TEST_STR).unwrap(), (13, 30));
     }
 }
@@ -1,3 +1,8 @@
+use crate::{
+    input::{blocks, empty_input, Layout, ParseResult},
+    solver::{Registration, Solver},
+};
+
 #[derive(Clone, Copy, Debug)]
 struct Span(isize, isize);
 
@@ -7,15 +12,76 @@ impl Span {
     }
 }
 
-pub fn solve(s: &str) -> (isize, isize) {
-    let mut parsed = parse(s);
-    let mut outputs: Vec<_> = Vec::new();
-    (
-        run(&parsed.maps, &mut parsed.p1_seeds, &mut outputs),
-        run(&parsed.maps, &mut parsed.p2_seeds, &mut outputs),
-    )
+pub struct Day05;
+
+impl Solver for Day05 {
+    type Parsed = Parsed;
+    type Answer1 = isize;
+    type Answer2 = isize;
+
+    const YEAR: u16 = 2023;
+    const DAY: u8 = 5;
+    const TITLE: &'static str = "If You Give A Seed A Fertilizer";
+    const LAYOUT: Layout = Layout::Blocks;
+
+    fn parse(s: &str) -> ParseResult<Self::Parsed> {
+        let mut blocks = blocks(s);
+        let first = blocks.next().ok_or_else(empty_input)?;
+        let [first] = first[..] else {
+            return Err(first[1].error("expected a blank line after the seeds"));
+        };
+        let (_, seeds) = first.split_once(first.text, "seeds:")?;
+        let seeds = seeds
+            .split_ascii_whitespace()
+            .map(|n| first.parse::<isize>(n, "seed"))
+            .collect::<ParseResult<Vec<_>>>()?;
+        if seeds.len() % 2 != 0 {
+            return Err(first.error("expected pairs of seed ranges"));
+        }
+        let p1_seeds: Vec<_> = seeds.iter().map(|n| Span(*n, *n)).collect();
+        let p2_seeds: Vec<_> = seeds
+            .chunks_exact(2)
+            .map(|c| Span(c[0], c[0] + c[1] - 1))
+            .collect();
+        // Each map is a block of a name, like "seed-to-soil map:", and ranges
+        let mut maps: Vec<_> = Vec::new();
+        for block in blocks {
+            let (name, ranges) = block.split_first().expect("blocks are not empty");
+            if !name.text.trim().ends_with("map:") {
+                return Err(name.error("expected a map name, like \"seed-to-soil map:\""));
+            }
+            let mut map = Vec::with_capacity(ranges.len());
+            for line in ranges {
+                let ns = line
+                    .text
+                    .split_ascii_whitespace()
+                    .map(|n| line.parse::<isize>(n, "number"))
+                    .collect::<ParseResult<Vec<_>>>()?;
+                let [dst, src, len] = ns[..] else {
+                    return Err(line.error("expected 3 numbers"));
+                };
+                map.push((Span(src, src + len - 1), dst - src));
+            }
+            maps.push(map);
+        }
+        Ok(Parsed {
+            p1_seeds,
+            p2_seeds,
+            maps,
+        })
+    }
+
+    fn part1(parsed: &Self::Parsed) -> isize {
+        run(&parsed.maps, &mut parsed.p1_seeds.clone(), &mut Vec::new())
+    }
+
+    fn part2(parsed: &Self::Parsed) -> isize {
+        run(&parsed.maps, &mut parsed.p2_seeds.clone(), &mut Vec::new())
+    }
 }
 
+inventory::submit! { Registration::new::<Day05>() }
+
 fn run(maps: &[Vec<(Span, isize)>], inputs: &mut Vec<Span>, outputs: &mut Vec<Span>) -> isize {
     for map in maps.iter() {
         translate(map, inputs, outputs);
@@ -114,52 +180,16 @@ fn intersection(src: Span, dst: Span) -> Option<Intersection> {
     }
 }
 
-struct Parsed {
+pub struct Parsed {
     p1_seeds: Vec<Span>,
     p2_seeds: Vec<Span>,
     maps: Vec<Vec<(Span, isize)>>,
 }
 
-fn parse(s: &str) -> Parsed {
-    let mut lines = s.lines().map(str::trim).filter(|s| !s.is_empty());
-    let seeds: Vec<_> = lines
-        .next()
-        .unwrap()
-        .split_ascii_whitespace()
-        .skip(1)
-        .map(|n| n.parse::<isize>().unwrap())
-        .collect();
-    let p1_seeds: Vec<_> = seeds.iter().map(|n| Span(*n, *n)).collect();
-    let p2_seeds: Vec<_> = seeds
-        .chunks_exact(2)
-        .map(|c| Span(c[0], c[0] + c[1] - 1))
-        .collect();
-    let mut maps: Vec<_> = Vec::new();
-    for line in lines {
-        if line.starts_with(|c: char| c.is_ascii_alphabetic()) {
-            maps.push(Vec::new());
-        } else {
-            let mut ns = line
-                .split_ascii_whitespace()
-                .map(|n| n.parse::<isize>().unwrap());
-            let dst = ns.next().unwrap();
-            let src = ns.next().unwrap();
-            let len = ns.next().unwrap();
-            let elem = (Span(src, src + len - 1), dst - src);
-            if let Some(v) = maps.last_mut() {
-                v.push(elem)
-            }
-        }
-    }
-    Parsed {
-        p1_seeds,
-        p2_seeds,
-        maps,
-    }
-}
-
 #[cfg(test)]
 mod tests {
+    use crate::{input::dedent, solver::Solver};
+
     static TEST_STR: &str = "seeds: 79 14 55 13
 
     seed-to-soil map:
@@ -196,6 +226,6 @@ mod tests {
 
     #[test]
     fn test() {
-        assert_eq!(super::solve(TEST_STR), (35, 46));
+        assert_eq!(super::Day05::solve(&dedent(TEST_STR)).unwrap(), (35, 46));
     }
 }
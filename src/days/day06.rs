@@ -1,27 +1,72 @@
-fn parse(s: &str) -> (Vec<(usize, usize)>, (usize, usize)) {
-    let mut lines = s
-        .lines()
-        .map(str::trim)
-        .filter(|s| !s.is_empty())
-        .map(|line| {
-            line.split_ascii_whitespace()
-                .skip(1)
-                .map(|n| n.parse::<usize>().unwrap())
-        });
-    let shift = |a, b: usize| a * (10_usize.pow(b.ilog10() + 1)) + b;
-    let p1: Vec<_> = lines.next().unwrap().zip(lines.next().unwrap()).collect();
-    let p2 = p1
-        .iter()
-        .fold((0, 0), |(a, b), (i, j)| (shift(a, *i), shift(b, *j)));
-    (p1, p2)
+use crate::{
+    input::{lines, Line, ParseError, ParseResult},
+    solver::{Registration, Solver},
+};
+
+pub struct Races {
+    races: Vec<(usize, usize)>,
+    // The single race when the spaces between numbers are ignored
+    kerned: (usize, usize),
 }
 
-fn solve_parsed(v: &[(usize, usize)]) -> usize {
-    v.iter()
-        .map(|(time, distance)| solve_race(*time, *distance))
-        .product::<usize>()
+pub struct Day06;
+
+impl Solver for Day06 {
+    type Parsed = Races;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    const YEAR: u16 = 2023;
+    const DAY: u8 = 6;
+    const TITLE: &'static str = "Wait For It";
+
+    fn parse(s: &str) -> ParseResult<Self::Parsed> {
+        let lines: Vec<_> = lines(s).collect();
+        let [time, distance] = lines[..] else {
+            let line = lines.get(2).map_or(1, |line| line.number);
+            return Err(ParseError::new(
+                line,
+                "expected a line of times and one of distances",
+            ));
+        };
+        let numbers = |line: Line, name: &str| -> ParseResult<Vec<usize>> {
+            let (_, numbers) = line.split_once(line.text, name)?;
+            numbers
+                .split_ascii_whitespace()
+                .map(|n| line.parse(n, "number"))
+                .collect()
+        };
+        let (times, distances) = (numbers(time, "Time:")?, numbers(distance, "Distance:")?);
+        if times.len() != distances.len() {
+            return Err(distance.error(format!("expected {} distances", times.len())));
+        }
+        let shift = |a, b: usize| a * (10_usize.pow(b.checked_ilog10().unwrap_or(0) + 1)) + b;
+        let p1: Vec<_> = times.into_iter().zip(distances).collect();
+        let p2 = p1
+            .iter()
+            .fold((0, 0), |(a, b), (i, j)| (shift(a, *i), shift(b, *j)));
+        Ok(Races {
+            races: p1,
+            kerned: p2,
+        })
+    }
+
+    fn part1(races: &Self::Parsed) -> usize {
+        races
+            .races
+            .iter()
+            .map(|(time, distance)| solve_race(*time, *distance))
+            .product::<usize>()
+    }
+
+    fn part2(races: &Self::Parsed) -> usize {
+        let (time, distance) = races.kerned;
+        solve_race(time, distance)
+    }
 }
 
+inventory::submit! { Registration::new::<Day06>() }
+
 fn solve_race(time: usize, distance: usize) -> usize {
     let d = (time * time - 4 * distance) as f64;
     let min = (((-(time as f64) + d.sqrt()) / -2.0) + 1.0).floor() as usize;
@@ -29,18 +74,15 @@ fn solve_race(time: usize, distance: usize) -> usize {
     max - min + 1
 }
 
-pub fn solve(s: &str) -> (usize, usize) {
-    let (v, (time, distance)) = parse(s);
-    (solve_parsed(&v), solve_race(time, distance))
-}
-
 #[cfg(test)]
 mod tests {
+    use crate::solver::Solver;
+
     static TEST_STR: &str = "Time:      7  15   30
     Distance:  9  40  200";
 
     #[test]
     fn test() {
-        assert_eq!(super::solve(TEST_STR), (288, 71503));
+        assert_eq!(super::Day06::solve(TEST_STR).unwrap(), (288, 71503));
     }
 }
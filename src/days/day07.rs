@@ -1,3 +1,7 @@
+use crate::{
+    input::{lines, ParseResult},
+    solver::{Registration, Solver},
+};
 use std::cmp::Ordering;
 
 #[derive(Debug, Clone, Copy, PartialOrd, Ord, PartialEq, Eq)]
@@ -12,7 +16,7 @@ enum Type {
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
-struct Hand {
+pub struct Hand {
     cards: [u8; 5],
     type_p1: Type,
     type_p2: Type,
@@ -38,19 +42,18 @@ impl Hand {
         }
     }
 
-    fn from_bytes(bytes: [u8; 5]) -> Self {
-        let cards = bytes.map(|byte| {
-            if (b'2'..=b'9').contains(&byte) {
-                return byte - b'2';
-            } else {
-                for (i, b) in [b'T', b'J', b'Q', b'K', b'A'].iter().enumerate() {
-                    if byte == *b {
-                        return (i + 8) as u8;
-                    }
-                }
-            }
-            unreachable!()
-        });
+    // The value of a card, from 0 for '2' to 12 for 'A'
+    fn card_value(byte: u8) -> Option<u8> {
+        if (b'2'..=b'9').contains(&byte) {
+            return Some(byte - b'2');
+        }
+        [b'T', b'J', b'Q', b'K', b'A']
+            .iter()
+            .position(|&b| b == byte)
+            .map(|i| (i + 8) as u8)
+    }
+
+    fn from_cards(cards: [u8; 5]) -> Self {
         let mut counts = [0u8; 13];
         for card in cards.iter() {
             counts[*card as usize] += 1;
@@ -86,32 +89,64 @@ impl Hand {
     }
 }
 
-fn parse(s: &str) -> Vec<(Hand, usize)> {
-    s.lines()
-        .map(str::trim)
-        .filter(|s| !s.is_empty())
-        .map(|line| {
-            let (h, n) = line.split_once(' ').unwrap();
-            (
-                Hand::from_bytes(h.as_bytes().try_into().unwrap()),
-                n.parse().unwrap(),
-            )
-        })
-        .collect::<Vec<_>>()
+pub struct Day07;
+
+impl Solver for Day07 {
+    type Parsed = Vec<(Hand, usize)>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    const YEAR: u16 = 2023;
+    const DAY: u8 = 7;
+    const TITLE: &'static str = "Camel Cards";
+
+    fn parse(s: &str) -> ParseResult<Self::Parsed> {
+        lines(s)
+            .map(|line| {
+                let (h, n) = line.split_once(line.text, " ")?;
+                let bytes: [u8; 5] = h
+                    .as_bytes()
+                    .try_into()
+                    .map_err(|_| line.error_at(h, format!("expected 5 cards, not '{h}'")))?;
+                let mut cards = [0; 5];
+                for (i, (card, byte)) in cards.iter_mut().zip(bytes).enumerate() {
+                    *card = Hand::card_value(byte).ok_or_else(|| {
+                        // The cards before are ASCII, so `i` is a character boundary
+                        let at = &h[i..];
+                        let symbol = at.chars().next().unwrap_or('?');
+                        line.error_at(at, format!("invalid card '{symbol}'"))
+                    })?;
+                }
+                Ok((Hand::from_cards(cards), line.parse(n.trim(), "bid")?))
+            })
+            .collect()
+    }
+
+    fn part1(v: &Self::Parsed) -> usize {
+        total_winnings(v, Hand::cmp_p1)
+    }
+
+    fn part2(v: &Self::Parsed) -> usize {
+        total_winnings(v, Hand::cmp_p2)
+    }
 }
 
-pub fn solve(s: &str) -> (usize, usize) {
-    let mut hands = parse(s);
-    let get_order = |v: &[(Hand, usize)]| v.iter().enumerate().map(|(i, (_, n))| (i + 1) * n).sum();
-    hands.sort_unstable_by(|a, b| Hand::cmp_p1(&a.0, &b.0));
-    let p1 = get_order(&hands);
-    hands.sort_unstable_by(|a, b| Hand::cmp_p2(&a.0, &b.0));
-    let p2 = get_order(&hands);
-    (p1, p2)
+inventory::submit! { Registration::new::<Day07>() }
+
+fn total_winnings(v: &[(Hand, usize)], cmp: fn(&Hand, &Hand) -> Ordering) -> usize {
+    let mut hands = v.to_vec();
+    hands.sort_unstable_by(|a, b| cmp(&a.0, &b.0));
+    hands
+        .iter()
+        .enumerate()
+        .map(|(i, (_, n))| (i + 1) * n)
+        .sum()
 }
 
 #[cfg(test)]
 mod tests {
+    use crate::solver::Solver;
+
     static TEST_STR: &str = "32T3K 765
     T55J5 684
     KK677 28
@@ -120,6 +155,15 @@ mod tests {
 
     #[test]
     fn test() {
-        assert_eq!(super::solve(TEST_STR), (6440, 5905));
+        assert_eq!(super::Day07::solve(TEST_STR).unwrap(), (6440, 5905));
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let input = TEST_STR.replace("KK677", "KK6X7");
+        assert_eq!(
+            super::Day07::parse(&input).err().unwrap().to_string(),
+            "input error at line 3, column 4: invalid card 'X'"
+        );
     }
 }
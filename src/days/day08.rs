@@ -1,17 +1,24 @@
+use crate::{
+    input::{empty_input, lines, Line, ParseError, ParseResult},
+    params::{Param, Params},
+    solver::{Registration, Solver},
+};
 use num;
 use std::collections::HashMap;
 
 // This struct identifies a code such as e.g. AKX
-// The uppermost bit tells whether it ends with Z
+// The uppermost bit tells whether it is an end node, by default one ending with Z
+const MAX_NODES: usize = 0x8000;
+
 #[derive(Debug, Clone, Copy)]
-struct Identifier(u16);
+pub struct Identifier(u16);
 
 impl Identifier {
-    fn from(s: &str, x: u16) -> Self {
-        if x > 0x7fff {
-            panic!()
+    fn from(s: &str, x: u16, end: char) -> Self {
+        if x as usize >= MAX_NODES {
+            panic!("Too many nodes")
         }
-        Self((((s.as_bytes().last().unwrap() == &b'Z') as u16) << 15) | x)
+        Self(((s.ends_with(end) as u16) << 15) | x)
     }
 }
 
@@ -25,14 +32,22 @@ impl Pair {
     }
 }
 
-struct Map(Vec<Pair>);
+pub struct Map(Vec<Pair>);
 
 impl Map {
-    fn from(h: HashMap<&str, (&str, &str)>) -> (Self, Identifier, Vec<Identifier>) {
-        // Validate that all (left, right) are keys in the map
-        for (left, right) in h.values() {
-            if !(h.contains_key(left) && h.contains_key(right)) {
-                panic!()
+    fn from(
+        h: HashMap<&str, (Line, &str, &str)>,
+        markers: &Markers,
+    ) -> ParseResult<(Self, Option<Identifier>, Vec<Identifier>)> {
+        // Validate that all (left, right) are keys in the map, reporting the
+        // first line referring to an unknown node
+        let mut entries: Vec<_> = h.values().collect();
+        entries.sort_by_key(|(line, _, _)| line.number);
+        for (line, left, right) in entries {
+            for node in [left, right] {
+                if !h.contains_key(node) {
+                    return Err(line.error_at(node, format!("unknown node '{node}'")));
+                }
             }
         }
 
@@ -42,21 +57,23 @@ impl Map {
             to_integer.insert(*k, len);
         }
 
-        let p1_start = Identifier(*to_integer.iter().find(|(&k, _)| k == "AAA").unwrap().1);
+        let p1_start = to_integer
+            .get(markers.start.as_str())
+            .map(|&i| Identifier(i));
         let p2_starts = to_integer
             .iter()
-            .filter(|(k, _)| k.ends_with('A'))
+            .filter(|(k, _)| k.ends_with(markers.ghost_start))
             .map(|(_, v)| Identifier(*v))
             .collect();
 
         let mut v: Vec<Pair> = vec![Pair([Identifier(0), Identifier(0)]); h.len()];
-        for (k, (l, r)) in h.iter() {
+        for (k, (_, l, r)) in h.iter() {
             v[to_integer[k] as usize] = Pair([
-                Identifier::from(l, *to_integer.get(l).unwrap()),
-                Identifier::from(r, *to_integer.get(r).unwrap()),
+                Identifier::from(l, *to_integer.get(l).unwrap(), markers.end),
+                Identifier::from(r, *to_integer.get(r).unwrap(), markers.end),
             ])
         }
-        (Self(v), p1_start, p2_starts)
+        Ok((Self(v), p1_start, p2_starts))
     }
 
     fn get(&self, i: Identifier) -> Pair {
@@ -64,41 +81,114 @@ impl Map {
     }
 }
 
-pub fn solve(s: &str) -> (usize, usize) {
-    let (sides, map, p1_start, p2_starts) = parse(s);
-    let p1 = get_cycle_length(&sides, &map, p1_start);
-    let p2_cycle_lengths: Vec<_> = p2_starts
-        .iter()
-        .map(|&u| get_cycle_length(&sides, &map, u))
-        .collect();
-    let p2 = p2_cycle_lengths
-        .iter()
-        .fold(1usize, |acc, &i| num::integer::lcm(acc, i));
-    (p1, p2)
+// Where the walks start and end
+struct Markers {
+    // The start node of part 1
+    start: String,
+    // The last letter of the start nodes of part 2
+    ghost_start: char,
+    // The last letter of the end nodes of both parts
+    end: char,
+}
+
+pub struct Network {
+    sides: Vec<bool>,
+    map: Map,
+    // An error if there is no start node, as in the examples of part 2
+    p1_start: ParseResult<Identifier>,
+    p2_starts: Vec<Identifier>,
 }
 
-fn parse(s: &str) -> (Vec<bool>, Map, Identifier, Vec<Identifier>) {
-    let mut lines = s.lines().map(str::trim).filter(|s| !s.is_empty());
-    let sides: Vec<_> = lines
-        .next()
-        .unwrap()
-        .as_bytes()
-        .iter()
-        .map(|&b| b == b'R')
-        .collect();
-
-    // Build a HashMap of the (from) -> (left, right) mappings
-    let mut string_map: HashMap<&str, (&str, &str)> = HashMap::new();
-    for line in lines {
-        let (from, x) = line.split_once(" = (").unwrap();
-        let (left, right) = x.strip_suffix(')').unwrap().split_once(", ").unwrap();
-        string_map.insert(from, (left, right));
+pub struct Day08;
+
+impl Solver for Day08 {
+    type Parsed = Network;
+    type Answer1 = ParseResult<usize>;
+    type Answer2 = usize;
+
+    const YEAR: u16 = 2023;
+    const DAY: u8 = 8;
+    const TITLE: &'static str = "Haunted Wasteland";
+    const PARAMS: &'static [Param] = &[
+        Param::new::<String>("start", "AAA", "The start node of part 1"),
+        Param::new::<char>(
+            "ghost_start",
+            "A",
+            "The last letter of the start nodes of part 2",
+        ),
+        Param::new::<char>("end", "Z", "The last letter of the end nodes"),
+    ];
+
+    fn parse(s: &str) -> ParseResult<Self::Parsed> {
+        Self::parse_with(s, &Params::defaults(Self::PARAMS))
     }
 
-    let (map, p1_start, p2_starts) = Map::from(string_map);
-    (sides, map, p1_start, p2_starts)
+    fn parse_with(s: &str, params: &Params) -> ParseResult<Self::Parsed> {
+        let markers = Markers {
+            start: params.get("start"),
+            ghost_start: params.get("ghost_start"),
+            end: params.get("end"),
+        };
+        let mut lines = lines(s);
+        let first = lines.next().ok_or_else(empty_input)?;
+        let mut sides = Vec::with_capacity(first.text.len());
+        for (i, b) in first.text.bytes().enumerate() {
+            match b {
+                b'L' | b'R' => sides.push(b == b'R'),
+                _ => {
+                    let instruction = &first.text[i..];
+                    return Err(first.error_at(instruction, "expected 'L' or 'R'"));
+                }
+            }
+        }
+
+        // Build a HashMap of the (from) -> (left, right) mappings
+        let mut string_map: HashMap<&str, (Line, &str, &str)> = HashMap::new();
+        let mut lines = lines.peekable();
+        let first_node = lines.peek().map_or(first.number, |line| line.number);
+        for line in lines {
+            let (from, x) = line.split_once(line.text, " = (")?;
+            let x = x
+                .strip_suffix(')')
+                .ok_or_else(|| line.error("expected ')' at the end"))?;
+            let (left, right) = line.split_once(x, ", ")?;
+            if string_map.len() == MAX_NODES {
+                return Err(line.error(format!("more than {MAX_NODES} nodes")));
+            }
+            if string_map.insert(from, (line, left, right)).is_some() {
+                return Err(line.error_at(from, format!("node '{from}' is defined twice")));
+            }
+        }
+
+        let (map, p1_start, p2_starts) = Map::from(string_map, &markers)?;
+        let p1_start = p1_start.ok_or_else(|| {
+            let message = format!("there is no start node '{}'", markers.start);
+            ParseError::new(first_node, message)
+        });
+        Ok(Network {
+            sides,
+            map,
+            p1_start,
+            p2_starts,
+        })
+    }
+
+    fn part1(network: &Self::Parsed) -> ParseResult<usize> {
+        let start = network.p1_start.clone()?;
+        Ok(get_cycle_length(&network.sides, &network.map, start))
+    }
+
+    fn part2(network: &Self::Parsed) -> usize {
+        network
+            .p2_starts
+            .iter()
+            .map(|&u| get_cycle_length(&network.sides, &network.map, u))
+            .fold(1usize, num::integer::lcm)
+    }
 }
 
+inventory::submit! { Registration::new::<Day08>() }
+
 fn get_cycle_length(sides: &[bool], map: &Map, start: Identifier) -> usize {
     let mut pair = map.get(start);
     for (step, side) in sides.iter().cycle().enumerate() {
@@ -110,3 +200,87 @@ fn get_cycle_length(sides: &[bool], map: &Map, start: Identifier) -> usize {
     }
     unreachable!()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::Day08;
+    use crate::{params::Params, solver::Solver};
+
+    static TEST_STR: &str = "RL
+
+    AAA = (BBB, CCC)
+    BBB = (DDD, EEE)
+    CCC = (ZZZ, GGG)
+    DDD = (DDD, DDD)
+    EEE = (EEE, EEE)
+    GGG = (GGG, GGG)
+    ZZZ = (ZZZ, ZZZ)";
+
+    static TEST_STR_2: &str = "LLR
+
+    AAA = (BBB, BBB)
+    BBB = (AAA, ZZZ)
+    ZZZ = (ZZZ, ZZZ)";
+
+    static TEST_STR_3: &str = "LR
+
+    11A = (11B, XXX)
+    11B = (XXX, 11Z)
+    11Z = (11B, XXX)
+    22A = (22B, XXX)
+    22B = (22C, 22C)
+    22C = (22Z, 22Z)
+    22Z = (22B, 22B)
+    XXX = (XXX, XXX)";
+
+    #[test]
+    fn test() {
+        assert_eq!(Day08::part1(&Day08::parse(TEST_STR).unwrap()), Ok(2));
+        assert_eq!(Day08::part1(&Day08::parse(TEST_STR_2).unwrap()), Ok(6));
+        let network = Day08::parse(TEST_STR_3).unwrap();
+        assert_eq!(Day08::part2(&network), 6);
+        // The example of part 2 has no start node for part 1
+        assert_eq!(
+            Day08::part1(&network).unwrap_err().to_string(),
+            "input error at line 3: there is no start node 'AAA'"
+        );
+    }
+
+    fn parse_with(s: &str, assignments: &[(&str, &str)]) -> super::Network {
+        let assignments: Vec<_> = assignments
+            .iter()
+            .map(|&(name, value)| (name.to_owned(), value.to_owned()))
+            .collect();
+        let params = Params::new(Day08::PARAMS, &assignments).unwrap();
+        Day08::parse_with(s, &params).unwrap()
+    }
+
+    #[test]
+    fn test_params() {
+        let network = parse_with(TEST_STR_3, &[("start", "11A")]);
+        assert_eq!(Day08::part1(&network), Ok(2));
+        assert_eq!(Day08::part2(&network), 6);
+        // AAA goes right to CCC
+        let network = parse_with(TEST_STR, &[("end", "C")]);
+        assert_eq!(Day08::part1(&network), Ok(1));
+        // BBB goes left to AAA, left to BBB and right to ZZZ
+        let network = parse_with(TEST_STR_2, &[("ghost_start", "B")]);
+        assert_eq!(Day08::part2(&network), 3);
+        let network = parse_with(TEST_STR, &[("start", "QQQ")]);
+        assert_eq!(
+            Day08::part1(&network).unwrap_err().to_string(),
+            "input error at line 3: there is no start node 'QQQ'"
+        );
+    }
+
+    #[test]
+    fn test_parse_error() {
+        assert_eq!(
+            Day08::parse(&TEST_STR_2.replace("(AAA, ZZZ)", "(AAA, YYY)"))
+                .err()
+                .unwrap()
+                .to_string(),
+            "input error at line 4, column 13: unknown node 'YYY'"
+        );
+    }
+}
@@ -1,45 +1,85 @@
-pub fn solve(s: &str) -> (isize, isize) {
+use crate::{
+    input::{lines, ParseResult},
+    solver::{Registration, Solver},
+};
+
+pub struct Day09;
+
+impl Solver for Day09 {
+    type Parsed = Vec<Vec<isize>>;
+    type Answer1 = isize;
+    type Answer2 = isize;
+
+    const YEAR: u16 = 2023;
+    const DAY: u8 = 9;
+    const TITLE: &'static str = "Mirage Maintenance";
+
+    fn parse(s: &str) -> ParseResult<Self::Parsed> {
+        lines(s)
+            .map(|line| {
+                line.text
+                    .split_ascii_whitespace()
+                    .map(|n| line.parse::<isize>(n, "number"))
+                    .collect()
+            })
+            .collect()
+    }
+
+    fn part1(v: &Self::Parsed) -> isize {
+        sum_extrapolated(v, false)
+    }
+
+    fn part2(v: &Self::Parsed) -> isize {
+        // Extrapolating backwards is extrapolating the reversed history
+        sum_extrapolated(v, true)
+    }
+}
+
+inventory::submit! { Registration::new::<Day09>() }
+
+// The sum of the next value of each history, or of the previous value if `backwards`
+fn sum_extrapolated(lines: &[Vec<isize>], backwards: bool) -> isize {
     let mut v: Vec<isize> = Vec::with_capacity(25);
-    let numbers = parse(s);
-    let (p1, p2) = numbers.fold((0, 0), |(p1, p2), it| {
-        v.clear();
-        v.extend(it);
-        let (line_p1, line_p2) = solve_parsed(&mut v);
-        (p1 + line_p1, p2 + line_p2)
-    });
-    (p1, p2)
+    lines
+        .iter()
+        .map(|line| {
+            v.clear();
+            v.extend_from_slice(line);
+            if backwards {
+                v.reverse();
+            }
+            extrapolate(&mut v)
+        })
+        .sum()
 }
 
-fn solve_parsed(v: &mut [isize]) -> (isize, isize) {
-    let mut offset = 0;
+// The next value of the sequence, which is the sum of the last elements of the
+// sequence and of all its differences. The differences are computed in place:
+// after `offset` rounds, v[offset..] holds the differences of that order.
+fn extrapolate(v: &mut [isize]) -> isize {
     let len = v.len();
+    let mut next = 0;
+    let mut offset = 0;
     while (offset..len).any(|i| v[i] != 0) {
-        let mut left = v[offset];
-        for i in v.iter_mut().skip(offset + 1) {
-            let right = *i;
-            *i -= left;
-            left = right;
+        next += v[len - 1];
+        for i in (offset + 1..len).rev() {
+            v[i] -= v[i - 1];
         }
         offset += 1;
     }
-    let mut rightest: isize = 0;
-    let mut leftest: isize = 0;
-    for i in (0..offset).rev() {
-        for j in (i + 1)..len {
-            v[j] += v[j - 1]
-        }
-        rightest += v[len - 1];
-        leftest = v[i] - leftest;
-    }
-    (rightest, leftest)
+    next
 }
 
-fn parse(s: &str) -> impl Iterator<Item = impl Iterator<Item = isize> + '_> + '_ {
-    s.lines()
-        .map(str::trim)
-        .filter(|s| !s.is_empty())
-        .map(|line| {
-            line.split_ascii_whitespace()
-                .map(|n| n.parse::<isize>().unwrap())
-        })
+#[cfg(test)]
+mod tests {
+    use crate::solver::Solver;
+
+    static TEST_STR: &str = "0 3 6 9 12 15
+    1 3 6 10 15 21
+    10 13 16 21 30 45";
+
+    #[test]
+    fn test() {
+        assert_eq!(super::Day09::solve(TEST_STR).unwrap(), (114, 2));
+    }
 }
@@ -1,5 +1,80 @@
-pub fn solve(s: &str) -> (usize, usize) {
-    let (start, map) = Map::parse(s);
+use crate::{
+    grid::{self, Direction, Grid, Pos},
+    input::{lines, Layout, ParseError, ParseResult},
+    solver::{Registration, Solver},
+    visualize::{paint, Picture, Svg, BOLD_RED, BOLD_YELLOW, DIM, GREEN},
+};
+
+pub struct Sketch {
+    start: Pos,
+    map: Grid<Pipe>,
+    main_loop: MainLoop,
+}
+
+pub struct Day10;
+
+impl Solver for Day10 {
+    type Parsed = Sketch;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    const YEAR: u16 = 2023;
+    const DAY: u8 = 10;
+    const TITLE: &'static str = "Pipe Maze";
+    const LAYOUT: Layout = Layout::Grid;
+
+    fn parse(s: &str) -> ParseResult<Self::Parsed> {
+        let map = Grid::parse(s, Pipe::from_byte)?;
+        let starts: Vec<Pos> = map
+            .iter()
+            .filter(|(_, &pipe)| pipe == Pipe::Start)
+            .map(|(pos, _)| pos)
+            .collect();
+        let start = match starts[..] {
+            [start] => start,
+            [] => {
+                let line = lines(s).last().map_or(1, |line| line.number);
+                return Err(ParseError::new(line, "no starting position 'S'"));
+            }
+            [_, second, ..] => return Err(grid::error_at(s, second, "second starting position")),
+        };
+        let main_loop = find_loop(s, &map, start)?;
+        Ok(Sketch {
+            start,
+            map,
+            main_loop,
+        })
+    }
+
+    fn part1(sketch: &Self::Parsed) -> usize {
+        // The farthest point is halfway along the loop
+        sketch.main_loop.path.len() / 2
+    }
+
+    fn part2(sketch: &Self::Parsed) -> usize {
+        enclosed(sketch)
+    }
+
+    fn visualize(sketch: &Self::Parsed, picture: Picture) -> Option<String> {
+        Some(match picture {
+            Picture::Text { color } => render_text(sketch, color),
+            Picture::Svg => render_svg(sketch),
+        })
+    }
+}
+
+inventory::submit! { Registration::new::<Day10>() }
+
+// The main loop: the positions along it, starting at the start, and the pipe
+// hidden under the start
+struct MainLoop {
+    path: Vec<Pos>,
+    start_pipe: Pipe,
+}
+
+// Follow the main loop through the grid parsed from `s`, reporting where it
+// breaks off
+fn find_loop(s: &str, map: &Grid<Pipe>, start: Pos) -> ParseResult<MainLoop> {
     let directions = [
         Direction::North,
         Direction::South,
@@ -8,161 +83,178 @@ pub fn solve(s: &str) -> (usize, usize) {
     ];
     let coords: Vec<_> = directions
         .iter()
-        .filter_map(|&d| match map.get_next(start, d) {
-            GetNextResult::Result(a, b) => Some((a, b)),
+        .filter_map(|&d| match get_next(map, start, d) {
+            GetNextResult::Result(a, b) => Some((d, a, b)),
             _ => None,
         })
         .collect();
     if coords.len() != 2 {
-        panic!("Not exactly two direction from starting position")
+        let message = format!(
+            "starting position connects to {} pipes, expected 2",
+            coords.len()
+        );
+        return Err(grid::error_at(s, start, message));
     };
-    let (mut coord, mut d) = coords[0];
-    let mut steps: usize = 1; // we have already taken step 1
-    let mut area: i64 = (start.0 * coord.1) as i64 - (coord.0 * start.1) as i64;
+    let (first_d, mut coord, mut d) = coords[0];
+    let mut path = vec![start, coord];
     loop {
-        steps += 1;
-        match map.get_next(coord, d) {
+        match get_next(map, coord, d) {
             GetNextResult::Done => {
-                area += (coord.0 * start.1) as i64 - (coord.1 * start.0) as i64;
-                area = area.abs() / 2;
-                let inner = area - ((steps / 2) as i64) + 1;
-                return (steps / 2, inner.try_into().unwrap());
+                // We enter the start going in direction d
+                let start_pipe = Pipe::between(first_d, d.opposite());
+                return Ok(MainLoop { path, start_pipe });
             }
             GetNextResult::Result(new_coord, new_d) => {
-                area += (coord.0 * new_coord.1) as i64 - (coord.1 * new_coord.0) as i64;
+                path.push(new_coord);
                 (coord, d) = (new_coord, new_d);
             }
-            _ => panic!(),
+            GetNextResult::OutOfBounds => {
+                return Err(grid::error_at(s, coord, "the loop leads off the map"))
+            }
+            GetNextResult::BadDirection(to_coord) => {
+                let message = "the loop is broken, this tile does not connect back";
+                return Err(grid::error_at(s, to_coord, message));
+            }
         }
     }
 }
 
-struct Map {
-    v: Vec<Pipe>,
-    y: usize,
-    x: usize,
+// The number of tiles enclosed by the loop
+fn enclosed(sketch: &Sketch) -> usize {
+    let path = &sketch.main_loop.path;
+    // The shoelace formula gives the area enclosed by the centers of the tiles
+    // of the loop, and Pick's theorem the tiles inside from that
+    let mut area: i64 = 0;
+    for (i, a) in path.iter().enumerate() {
+        let b = path[(i + 1) % path.len()];
+        area += (a.0 * b.1) as i64 - (a.1 * b.0) as i64;
+    }
+    let area = area.unsigned_abs() as usize / 2;
+    let steps = path.len();
+    // The loop does not cross itself, so area >= steps / 2 - 1
+    area + 1 - steps / 2
 }
 
-enum GetNextResult {
-    Done,
-    OutOfBounds,
-    BadDirection,
-    Result((usize, usize), Direction),
+/// Whether a tile is part of the main loop, enclosed by it, or outside it
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Tile {
+    Loop,
+    Inside,
+    Outside,
 }
 
-impl Map {
-    fn parse(s: &str) -> ((usize, usize), Self) {
-        let mut x: Option<usize> = None;
-        let mut coord: Option<(usize, usize)> = None;
-        let mut vec: Vec<Pipe> = Vec::new();
-        for (rownum, line) in s
-            .lines()
-            .map(str::trim)
-            .filter(|s| !s.is_empty())
-            .enumerate()
-        {
-            let bytes = line.as_bytes();
-            match x {
-                None => x = Some(bytes.len()),
-                Some(len) => {
-                    if len != bytes.len() {
-                        panic!("Unequal row lengths")
-                    }
-                }
-            };
-            for (colnum, &char) in bytes.iter().enumerate() {
-                let pipe = match &char {
-                    b'|' => Pipe::Vertical,
-                    b'-' => Pipe::Horizontal,
-                    b'.' => Pipe::Ground,
-                    b'J' => Pipe::NorthWest,
-                    b'F' => Pipe::SouthEast,
-                    b'7' => Pipe::SouthWest,
-                    b'L' => Pipe::NorthEast,
-                    b'S' => {
-                        if coord.is_some() {
-                            panic!("Two or more starting locations")
-                        } else {
-                            coord = Some((rownum, colnum));
-                            Pipe::Start
-                        }
-                    }
-                    _ => {
-                        panic!("Unknown symbol: {}", char::from_u32(char.into()).unwrap())
-                    }
+// Classify each tile. Scanning each row
+// from the west, we are inside the loop after crossing an odd number of loop
+// pipes that connect north.
+fn classify(sketch: &Sketch) -> Grid<Tile> {
+    let main_loop = &sketch.main_loop;
+    let map = &sketch.map;
+    let mut tiles = Grid::filled(map.rows(), map.cols(), Tile::Outside);
+    for &pos in main_loop.path.iter() {
+        tiles[pos] = Tile::Loop;
+    }
+    for row in 0..map.rows() {
+        let mut inside = false;
+        for col in 0..map.cols() {
+            let pos = (row, col);
+            if tiles[pos] == Tile::Loop {
+                let pipe = if pos == sketch.start {
+                    main_loop.start_pipe
+                } else {
+                    map[pos]
                 };
-                vec.push(pipe);
-            }
-        }
-        let map = match x {
-            None => panic!("Must be at least one row"),
-            Some(0) => panic!("Each row must have at least one element"),
-            Some(x) => {
-                let y = vec.len() / x;
-                Map { v: vec, x, y }
+                if pipe.connects(Direction::North) {
+                    inside = !inside
+                }
+            } else if inside {
+                tiles[pos] = Tile::Inside
             }
-        };
-        if let Some(c) = coord {
-            (c, map)
-        } else {
-            panic!("Could not find starting position")
         }
     }
+    tiles
+}
 
-    fn get_next(&self, from_coord: (usize, usize), direction: Direction) -> GetNextResult {
-        let to_coord = match self.update_coord(direction, from_coord) {
-            None => return GetNextResult::OutOfBounds,
-            Some(x) => x,
+fn render_text(sketch: &Sketch, color: bool) -> String {
+    let tiles = classify(sketch);
+    let main_loop = &sketch.main_loop;
+    let mut s = String::new();
+    for (pos, tile) in tiles.iter() {
+        let pipe = sketch.map[pos];
+        let symbol = pipe.box_drawing();
+        s += &match tile {
+            Tile::Loop if pos == sketch.start => {
+                paint(main_loop.start_pipe.box_drawing(), BOLD_RED, color)
+            }
+            Tile::Loop => paint(symbol, BOLD_YELLOW, color),
+            Tile::Inside if color => paint(symbol, GREEN, color),
+            Tile::Inside => "I".to_owned(),
+            Tile::Outside if color => paint(symbol, DIM, color),
+            Tile::Outside => "O".to_owned(),
         };
-        let (y, x) = to_coord;
-        let pipe = unsafe { self.v.get_unchecked(self.x * y + x) };
-        if matches!(pipe, Pipe::Start) {
-            return GetNextResult::Done;
+        if pos.1 + 1 == tiles.cols() {
+            s.push('\n');
         }
-        let new_dir = match new_direction(direction, *pipe) {
-            None => return GetNextResult::BadDirection,
-            Some(d) => d,
-        };
-        GetNextResult::Result(to_coord, new_dir)
     }
+    s
+}
 
-    fn update_coord(&self, direction: Direction, coord: (usize, usize)) -> Option<(usize, usize)> {
-        let (y, x) = coord;
-        match direction {
-            Direction::East => {
-                if x == self.x {
-                    None
-                } else {
-                    Some((y, x + 1))
-                }
-            }
-            Direction::South => {
-                if y == self.y {
-                    None
-                } else {
-                    Some((y + 1, x))
-                }
-            }
-            Direction::West => {
-                if x == 0 {
-                    None
-                } else {
-                    Some((y, x - 1))
-                }
-            }
-            Direction::North => {
-                if y == 0 {
-                    None
-                } else {
-                    Some((y - 1, x))
-                }
+fn render_svg(sketch: &Sketch) -> String {
+    const CELL: f64 = 10.0;
+    let tiles = classify(sketch);
+    let main_loop = &sketch.main_loop;
+    let center = |pos: Pos| ((pos.1 as f64 + 0.5) * CELL, (pos.0 as f64 + 0.5) * CELL);
+    let mut svg = Svg::new(tiles.cols() as f64 * CELL, tiles.rows() as f64 * CELL);
+    svg.rect(
+        0.0,
+        0.0,
+        tiles.cols() as f64 * CELL,
+        tiles.rows() as f64 * CELL,
+        "#eeeeee",
+    );
+    for (pos, &tile) in tiles.iter() {
+        if tile == Tile::Inside {
+            let (x, y) = (pos.1 as f64 * CELL, pos.0 as f64 * CELL);
+            svg.rect(x, y, CELL, CELL, "#7fc97f");
+        }
+        // Pipes that are not part of the loop are drawn faintly
+        if tile != Tile::Loop {
+            for d in sketch.map[pos].connections().into_iter().flatten() {
+                let (x, y) = center(pos);
+                let (dy, dx) = d.offset();
+                let end = (x + dx as f64 * CELL / 2.0, y + dy as f64 * CELL / 2.0);
+                svg.line((x, y), end, "#aaaaaa", 1.0);
             }
         }
     }
+    let points: Vec<_> = main_loop.path.iter().map(|&pos| center(pos)).collect();
+    svg.polygon(&points, "#d62728", 2.0);
+    svg.finish()
+}
+
+enum GetNextResult {
+    Done,
+    OutOfBounds,
+    /// The pipe at the position does not connect back
+    BadDirection(Pos),
+    Result(Pos, Direction),
+}
+
+fn get_next(map: &Grid<Pipe>, from_coord: Pos, direction: Direction) -> GetNextResult {
+    let Some(to_coord) = map.step(from_coord, direction) else {
+        return GetNextResult::OutOfBounds;
+    };
+    let pipe = map[to_coord];
+    if pipe == Pipe::Start {
+        return GetNextResult::Done;
+    }
+    match new_direction(direction, pipe) {
+        None => GetNextResult::BadDirection(to_coord),
+        Some(d) => GetNextResult::Result(to_coord, d),
+    }
 }
 
-#[derive(Clone, Copy, Debug)]
-enum Pipe {
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Pipe {
     Vertical,
     Horizontal,
     NorthEast,
@@ -173,12 +265,65 @@ enum Pipe {
     Start,
 }
 
-#[derive(Clone, Copy, Debug)]
-enum Direction {
-    North,
-    South,
-    East,
-    West,
+impl Pipe {
+    fn from_byte(byte: u8) -> Option<Self> {
+        Some(match byte {
+            b'|' => Pipe::Vertical,
+            b'-' => Pipe::Horizontal,
+            b'.' => Pipe::Ground,
+            b'J' => Pipe::NorthWest,
+            b'F' => Pipe::SouthEast,
+            b'7' => Pipe::SouthWest,
+            b'L' => Pipe::NorthEast,
+            b'S' => Pipe::Start,
+            _ => return None,
+        })
+    }
+
+    // The directions the pipe connects, if it is a pipe
+    fn connections(self) -> Option<[Direction; 2]> {
+        match self {
+            Pipe::Vertical => Some([Direction::North, Direction::South]),
+            Pipe::Horizontal => Some([Direction::East, Direction::West]),
+            Pipe::NorthEast => Some([Direction::North, Direction::East]),
+            Pipe::NorthWest => Some([Direction::North, Direction::West]),
+            Pipe::SouthEast => Some([Direction::South, Direction::East]),
+            Pipe::SouthWest => Some([Direction::South, Direction::West]),
+            Pipe::Ground | Pipe::Start => None,
+        }
+    }
+
+    fn connects(self, direction: Direction) -> bool {
+        self.connections().is_some_and(|c| c.contains(&direction))
+    }
+
+    // The pipe connecting the two directions
+    fn between(a: Direction, b: Direction) -> Self {
+        [
+            Pipe::Vertical,
+            Pipe::Horizontal,
+            Pipe::NorthEast,
+            Pipe::NorthWest,
+            Pipe::SouthEast,
+            Pipe::SouthWest,
+        ]
+        .into_iter()
+        .find(|pipe| pipe.connects(a) && pipe.connects(b))
+        .unwrap()
+    }
+
+    fn box_drawing(self) -> char {
+        match self {
+            Pipe::Vertical => '│',
+            Pipe::Horizontal => '─',
+            Pipe::NorthEast => '└',
+            Pipe::NorthWest => '┘',
+            Pipe::SouthEast => '┌',
+            Pipe::SouthWest => '┐',
+            Pipe::Ground => '·',
+            Pipe::Start => 'S',
+        }
+    }
 }
 
 fn new_direction(from: Direction, pipe: Pipe) -> Option<Direction> {
@@ -201,6 +346,7 @@ fn new_direction(from: Direction, pipe: Pipe) -> Option<Direction> {
 
 #[cfg(test)]
 mod tests {
+    use crate::{input::dedent, solver::Solver};
 
     static TEST_STR: &str = "..F7.
     .FJ|.
@@ -210,7 +356,7 @@ mod tests {
 
     #[test]
     fn test() {
-        assert_eq!(super::solve(TEST_STR), (8, 1));
+        assert_eq!(super::Day10::solve(&dedent(TEST_STR)).unwrap(), (8, 1));
     }
 
     static TEST_STR_2: &str = ".....
@@ -221,7 +367,7 @@ mod tests {
 
     #[test]
     fn test_2() {
-        assert_eq!(super::solve(TEST_STR_2), (4, 1));
+        assert_eq!(super::Day10::solve(&dedent(TEST_STR_2)).unwrap(), (4, 1));
     }
 
     static TEST_STR_3: &str = "...........
@@ -236,7 +382,7 @@ mod tests {
 
     #[test]
     fn test_3() {
-        assert_eq!(super::solve(TEST_STR_3), (23, 4));
+        assert_eq!(super::Day10::solve(&dedent(TEST_STR_3)).unwrap(), (23, 4));
     }
 
     static TEST_STR_4: &str = ".F----7F7F7F7F-7....
@@ -252,7 +398,7 @@ mod tests {
 
     #[test]
     fn test_4() {
-        assert_eq!(super::solve(TEST_STR_4), (70, 8));
+        assert_eq!(super::Day10::solve(&dedent(TEST_STR_4)).unwrap(), (70, 8));
     }
 
     static TEST_STR_5: &str = "..........
@@ -267,6 +413,62 @@ mod tests {
 
     #[test]
     fn test_5() {
-        assert_eq!(super::solve(TEST_STR_5), (22, 4));
+        assert_eq!(super::Day10::solve(&dedent(TEST_STR_5)).unwrap(), (22, 4));
+    }
+
+    #[test]
+    fn test_classify() {
+        for (s, inside) in [
+            (TEST_STR, 1),
+            (TEST_STR_3, 4),
+            (TEST_STR_4, 8),
+            (TEST_STR_5, 4),
+        ] {
+            let sketch = super::Day10::parse(&dedent(s)).unwrap();
+            let tiles = super::classify(&sketch);
+            let n_inside = tiles
+                .iter()
+                .filter(|(_, &t)| t == super::Tile::Inside)
+                .count();
+            assert_eq!(n_inside, inside);
+        }
+        let sketch = super::Day10::parse(&dedent(TEST_STR_2)).unwrap();
+        assert_eq!(
+            super::render_text(&sketch, false),
+            "OOOOO\nO┌─┐O\nO│I│O\nO└─┘O\nOOOOO\n"
+        );
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let error = |s: &str| super::Day10::parse(&dedent(s)).err().unwrap().to_string();
+        assert_eq!(
+            error(&TEST_STR.replace(".FJ|.", ".FJ|")),
+            "input error at line 2: row has length 4, expected 5"
+        );
+        assert_eq!(
+            error(&TEST_STR.replace("|F--J", "|F-xJ")),
+            "input error at line 4, column 4: unknown symbol 'x'"
+        );
+        assert_eq!(
+            error(&TEST_STR.replace('S', ".")),
+            "input error at line 5: no starting position 'S'"
+        );
+        assert_eq!(
+            error(&TEST_STR.replace(".FJ|.", "7FJ|.")),
+            "input error at line 3, column 1: starting position connects to 3 pipes, expected 2"
+        );
+        assert_eq!(
+            error(&TEST_STR.replace(".FJ|.", ".F7|.")),
+            "input error at line 2, column 3: the loop is broken, this tile does not connect back"
+        );
+        assert_eq!(
+            error(&TEST_STR_2.replace(".S-7.", ".S--.")),
+            "input error at line 2, column 4: the loop is broken, this tile does not connect back"
+        );
+        assert_eq!(
+            error("S-\nL7"),
+            "input error at line 2, column 2: the loop leads off the map"
+        );
     }
 }
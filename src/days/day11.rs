@@ -1,34 +1,74 @@
-pub fn solve(s: &str) -> (usize, usize) {
-    let (rows, cols) = parse(s);
-    (distance(&rows, &cols, 2), distance(&rows, &cols, 1_000_000))
+use crate::{
+    grid::Grid,
+    input::{Layout, ParseResult},
+    params::{Param, Params},
+    solver::{Registration, Solver},
+};
+
+pub struct Galaxies {
+    rows: Vec<u16>,
+    cols: Vec<u16>,
+    // The size of an empty row or column in each part
+    expansion: [usize; 2],
 }
 
-fn parse(s: &str) -> (Vec<u16>, Vec<u16>) {
-    let (mut rows, mut cols) = (Vec::new(), Vec::new());
-    let mut len: Option<usize> = None;
-    for line in s.lines().map(str::trim).filter(|s| !s.is_empty()) {
-        let bytes = line.as_bytes();
-        if let Some(x) = len {
-            if x != bytes.len() {
-                panic!();
-            }
-        } else {
-            len = Some(bytes.len());
-            rows.resize(bytes.len(), 0);
-        }
-        for (r, &b) in rows.iter_mut().zip(bytes.iter()) {
-            *r += (b == b'#') as u16
-        }
-        cols.push(
-            bytes
-                .iter()
-                .filter(|&&i| i == b'#')
-                .count()
-                .try_into()
-                .unwrap(),
-        )
+pub struct Day11;
+
+impl Solver for Day11 {
+    type Parsed = Galaxies;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    const YEAR: u16 = 2023;
+    const DAY: u8 = 11;
+    const TITLE: &'static str = "Cosmic Expansion";
+    const PARAMS: &'static [Param] = &[
+        Param::new::<usize>(
+            "expansion1",
+            "2",
+            "The size of an empty row or column in part 1",
+        ),
+        Param::new::<usize>(
+            "expansion2",
+            "1000000",
+            "The size of an empty row or column in part 2",
+        ),
+    ];
+    const LAYOUT: Layout = Layout::Grid;
+
+    fn parse(s: &str) -> ParseResult<Self::Parsed> {
+        Self::parse_with(s, &Params::defaults(Self::PARAMS))
+    }
+
+    fn parse_with(s: &str, params: &Params) -> ParseResult<Self::Parsed> {
+        let grid = Grid::parse(s, |b| match b {
+            b'#' => Some(true),
+            b'.' => Some(false),
+            _ => None,
+        })?;
+        Ok(Galaxies {
+            rows: grid
+                .iter_rows()
+                .map(|row| count_galaxies(row.iter()))
+                .collect(),
+            cols: grid.iter_columns().map(count_galaxies).collect(),
+            expansion: [params.get("expansion1"), params.get("expansion2")],
+        })
+    }
+
+    fn part1(galaxies: &Self::Parsed) -> usize {
+        distance(&galaxies.rows, &galaxies.cols, galaxies.expansion[0])
     }
-    (rows, cols)
+
+    fn part2(galaxies: &Self::Parsed) -> usize {
+        distance(&galaxies.rows, &galaxies.cols, galaxies.expansion[1])
+    }
+}
+
+inventory::submit! { Registration::new::<Day11>() }
+
+fn count_galaxies<'a>(line: impl Iterator<Item = &'a bool>) -> u16 {
+    line.filter(|&&galaxy| galaxy).count().try_into().unwrap()
 }
 
 fn distance(rows: &[u16], cols: &[u16], expansion: usize) -> usize {
@@ -57,6 +97,9 @@ fn distance_in_dimension(v: &[u16], expansion: usize) -> usize {
 
 #[cfg(test)]
 mod tests {
+    use super::Day11;
+    use crate::{input::dedent, params::Params, solver::Solver};
+
     static TEST_STR: &str = "...#......
     .......#..
     #.........
@@ -70,6 +113,19 @@ mod tests {
 
     #[test]
     fn test() {
-        assert_eq!(super::solve(TEST_STR).0, 374);
+        assert_eq!(Day11::solve(&dedent(TEST_STR)).unwrap().0, 374);
+    }
+
+    #[test]
+    fn test_expansion() {
+        // The examples of part 2
+        let assignments = [
+            ("expansion1".to_owned(), "10".to_owned()),
+            ("expansion2".to_owned(), "100".to_owned()),
+        ];
+        let params = Params::new(Day11::PARAMS, &assignments).unwrap();
+        let galaxies = Day11::parse_with(&dedent(TEST_STR), &params).unwrap();
+        assert_eq!(Day11::part1(&galaxies), 1030);
+        assert_eq!(Day11::part2(&galaxies), 8410);
     }
 }
@@ -0,0 +1,71 @@
+// Downloading inputs and puzzle descriptions into the data directory of a year.
+// Existing files are overwritten; callers decide what is worth downloading.
+
+use std::{path::Path, time::SystemTime};
+
+use crate::{
+    client::{AocClient, BASE_URL},
+    error::{Error, Result},
+    puzzle::{extract_articles, html_path, html_to_markdown, markdown_path},
+    runner::input_path,
+    year::Year,
+    Day,
+};
+
+/// Create the data directory if it does not exist
+pub fn create_data_dir(data_dir: &Path) -> Result<()> {
+    if !data_dir.exists() {
+        if data_dir.parent().is_none() {
+            return Err(Error::MissingDataDir(data_dir.to_owned()));
+        }
+        std::fs::create_dir_all(data_dir).map_err(|e| Error::io(data_dir, e))
+    } else if !data_dir.is_dir() {
+        Err(Error::MissingDataDir(data_dir.to_owned()))
+    } else {
+        Ok(())
+    }
+}
+
+/// Download the input of the day, and store it as dayNN.txt. Days that are not
+/// released yet are refused without asking the server.
+///
+/// ```no_run
+/// use advent2023::{client::AocClient, config::Config, download, Day, Year};
+/// use std::path::Path;
+///
+/// let client = AocClient::from_config(&Config::load()?)?;
+/// download::download_input(&client, Path::new("data/2023"), Year(2023), Day(1))?;
+/// # Ok::<(), advent2023::Error>(())
+/// ```
+pub fn download_input(client: &AocClient, data_dir: &Path, year: Year, day: Day) -> Result<()> {
+    if !year.is_released(day, SystemTime::now()) {
+        return Err(Error::Unreleased(day));
+    }
+    create_data_dir(data_dir)?;
+    let data = client.download_input(year, day)?;
+    let path = input_path(data_dir, day);
+    std::fs::write(&path, data).map_err(|e| Error::io(path, e))
+}
+
+/// Download the puzzle description of the day, and store it as dayNN.html and,
+/// converted to Markdown, as dayNN.md
+pub fn download_puzzle(client: &AocClient, data_dir: &Path, year: Year, day: Day) -> Result<()> {
+    if !year.is_released(day, SystemTime::now()) {
+        return Err(Error::Unreleased(day));
+    }
+    create_data_dir(data_dir)?;
+    let page = client.download_puzzle(year, day)?;
+    let articles = extract_articles(&page);
+    if articles.is_empty() {
+        return Err(Error::BadResponse {
+            url: format!("{BASE_URL}/{year}/day/{}", day.0),
+            status: 200,
+            body: "No puzzle description found in page".to_owned(),
+        });
+    }
+    let html = articles.join("\n");
+    let path = html_path(data_dir, day);
+    std::fs::write(&path, &html).map_err(|e| Error::io(path, e))?;
+    let path = markdown_path(data_dir, day);
+    std::fs::write(&path, html_to_markdown(&html)).map_err(|e| Error::io(path, e))
+}
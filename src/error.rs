@@ -0,0 +1,213 @@
+use std::{fmt::Display, path::PathBuf};
+
+use crate::{input::ParseError, solver::Failure, year::Year, Day};
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+#[derive(Debug)]
+pub enum Error {
+    /// A day string could not be parsed as a day in 1-25
+    BadDay(String),
+    /// A year string could not be parsed as a year of Advent of Code
+    BadYear(String),
+    /// The event of the year has fewer days
+    NoSuchDay { year: Year, day: Day },
+    /// A token in a day selection is invalid
+    BadSelection { token: String, reason: String },
+    /// The day has no registered solver
+    Unimplemented(Day),
+    /// The solver of the day does not solve this part yet
+    UnimplementedPart { day: Day, part: u8 },
+    /// The solver of the day cannot draw its input
+    NoVisualization(Day),
+    /// A parameter of a day is unknown, or its value is invalid
+    BadParam(String),
+    /// The command line arguments are inconsistent, e.g. --all and a list of days
+    Usage(&'static str),
+    /// The data directory does not exist, or is not a directory
+    MissingDataDir(PathBuf),
+    /// The input file of an implemented day is not in the data directory
+    MissingInput { day: Day, path: PathBuf },
+    /// The puzzle description of the day has not been downloaded
+    MissingPuzzle { day: Day, path: PathBuf },
+    /// The input of the day is malformed
+    BadInput { day: Day, error: ParseError },
+    /// The inputs of this many days are malformed
+    BadInputs(usize),
+    /// The solver of the day panicked or timed out
+    SolverFailed { day: Day, failure: Failure },
+    /// The solvers of this many days panicked or timed out
+    SolverFailures(usize),
+    /// Any other I/O error when reading or writing a file
+    Io {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+    /// A file with stored answers could not be parsed
+    BadAnswers { path: PathBuf, reason: String },
+    /// The configuration file could not be parsed
+    BadConfig { path: PathBuf, reason: String },
+    /// Some answers differ from the stored answers
+    WrongAnswers(usize),
+    /// The request could not be sent, or the response could not be read
+    Http {
+        url: String,
+        attempts: u32,
+        source: reqwest::Error,
+    },
+    /// The server responded with an error that we don't know how to handle
+    BadResponse {
+        url: String,
+        status: u16,
+        body: String,
+    },
+    /// The session token could not be loaded
+    MissingSession(String),
+    /// The file holding the session token can be accessed by other users
+    InsecureSession(PathBuf),
+    /// The server does not accept the session token
+    ExpiredSession,
+    /// The puzzle of the given day is not yet released
+    Unreleased(Day),
+    /// A submitted answer was not accepted by the server
+    AnswerRejected,
+}
+
+impl Error {
+    pub fn io(path: impl Into<PathBuf>, source: std::io::Error) -> Self {
+        Error::Io {
+            path: path.into(),
+            source,
+        }
+    }
+
+    /// The error of a day that could not be solved
+    pub fn failure(day: Day, failure: Failure) -> Self {
+        match failure {
+            Failure::BadInput(error) => Error::BadInput { day, error },
+            failure => Error::SolverFailed { day, failure },
+        }
+    }
+
+    /// The exit code of the process if it terminates with this error.
+    /// These are stable, such that scripts can distinguish the errors.
+    pub fn exit_code(&self) -> u8 {
+        match self {
+            Error::BadDay(_)
+            | Error::BadYear(_)
+            | Error::NoSuchDay { .. }
+            | Error::BadSelection { .. }
+            | Error::Usage(_)
+            | Error::BadParam(_)
+            | Error::Unimplemented(_)
+            | Error::UnimplementedPart { .. }
+            | Error::NoVisualization(_) => 2,
+            Error::MissingDataDir(_) => 3,
+            Error::MissingInput { .. } | Error::MissingPuzzle { .. } => 4,
+            Error::Io { .. } | Error::BadAnswers { .. } | Error::BadConfig { .. } => 5,
+            Error::Http { .. } | Error::BadResponse { .. } => 6,
+            Error::MissingSession(_) | Error::InsecureSession(_) | Error::ExpiredSession => 7,
+            Error::Unreleased(_) => 8,
+            Error::WrongAnswers(_) => 9,
+            Error::AnswerRejected => 10,
+            Error::BadInput { .. } | Error::BadInputs(_) => 11,
+            Error::SolverFailed { .. } | Error::SolverFailures(_) => 12,
+        }
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::BadDay(s) => write!(f, "Cannot parse \"{s}\" as integer in 1-25"),
+            Error::BadYear(s) => {
+                write!(f, "\"{s}\" is not a year of Advent of Code (2015 or later)")
+            }
+            Error::NoSuchDay { year, day } => write!(
+                f,
+                "There is no day {:02} in {year}, which has {} days",
+                day.0,
+                year.days()
+            ),
+            Error::BadSelection { token, reason } => {
+                write!(f, "Invalid day selection \"{token}\": {reason}")
+            }
+            Error::Usage(s) => f.write_str(s),
+            Error::BadParam(s) => f.write_str(s),
+            Error::Unimplemented(day) => write!(f, "Day {:02} is not implemented", day.0),
+            Error::UnimplementedPart { day, part } => {
+                write!(f, "Part {part} of day {:02} is not implemented yet", day.0)
+            }
+            Error::NoVisualization(day) => write!(f, "Day {:02} has no visualization", day.0),
+            Error::MissingDataDir(path) => {
+                write!(f, "Data directory is not an existing directory: {:?}", path)
+            }
+            Error::MissingInput { day, path } => write!(
+                f,
+                "Data file of day {:02} not found at path {:?}",
+                day.0, path
+            ),
+            Error::MissingPuzzle { day, path } => write!(
+                f,
+                "Puzzle of day {:02} not found at path {:?}. Download it with `download --puzzle`",
+                day.0, path
+            ),
+            Error::BadInput { day, error } => write!(f, "Day {:02}: {error}", day.0),
+            Error::BadInputs(n) => write!(f, "The input of {n} day(s) could not be parsed"),
+            Error::SolverFailed { day, failure } => write!(f, "Day {:02}: {failure}", day.0),
+            Error::SolverFailures(n) => write!(f, "The solver of {n} day(s) panicked or timed out"),
+            Error::Io { path, source } => write!(f, "I/O error at path {:?}: {}", path, source),
+            Error::BadAnswers { path, reason } => {
+                write!(f, "Malformed answers file {:?}: {}", path, reason)
+            }
+            Error::BadConfig { path, reason } => {
+                write!(f, "Malformed configuration file {:?}: {}", path, reason)
+            }
+            Error::WrongAnswers(n) => {
+                write!(f, "{n} answer(s) differ from the stored answers")
+            }
+            Error::Http {
+                url,
+                attempts,
+                source,
+            } => {
+                write!(f, "Request to {url} failed")?;
+                if *attempts > 1 {
+                    write!(f, " after {attempts} attempts")?;
+                }
+                // The messages of reqwest errors leave out the underlying cause
+                let mut error: Option<&dyn std::error::Error> = Some(source);
+                while let Some(e) = error {
+                    write!(f, ": {e}")?;
+                    error = e.source();
+                }
+                Ok(())
+            }
+            Error::BadResponse { url, status, body } => {
+                write!(f, "Request to {url} returned status {status}:\n{body}")
+            }
+            Error::MissingSession(s) => f.write_str(s),
+            Error::InsecureSession(path) => write!(
+                f,
+                "The session token in {:?} can be accessed by other users. Restrict it with `chmod 600 {}`",
+                path,
+                path.display()
+            ),
+            Error::ExpiredSession => f.write_str(
+                "The session token is invalid or expired. Log in to Advent of Code again, and update the token",
+            ),
+            Error::Unreleased(day) => write!(f, "Day {:02} is not released yet!", day.0),
+            Error::AnswerRejected => f.write_str("The answer was not accepted"),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Io { source, .. } => Some(source),
+            Error::Http { source, .. } => Some(source),
+            _ => None,
+        }
+    }
+}
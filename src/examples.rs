@@ -0,0 +1,327 @@
+// Examples from the puzzle descriptions are stored in the `examples` directory
+// of the data directory: examples/dayNN-K.txt is the K'th example of the day,
+// and examples/dayNN-K.answers holds its expected answers, in the same format
+// as the answers of the real input.
+//
+// Puzzle pages introduce example inputs in a <pre><code> block, typically after
+// a paragraph like "For example:". The expected answer of an example is the last
+// emphasized code, like <code><em>142</em></code>, after it and before the next
+// example.
+
+use std::path::{Path, PathBuf};
+
+use crate::{
+    answer::Answer,
+    answers::{Answers, Check},
+    error::{Error, Result},
+    isolate,
+    puzzle::{decode_entities, extract_articles, html_path},
+    runner::day_params,
+    solver::{Parts, Registration},
+    Day,
+};
+
+pub fn examples_dir(data_dir: &Path) -> PathBuf {
+    data_dir.join("examples")
+}
+
+fn example_path(data_dir: &Path, day: Day, index: usize) -> PathBuf {
+    examples_dir(data_dir).join(format!("day{:02}-{}.txt", day.0, index))
+}
+
+fn example_answers_path(data_dir: &Path, day: Day, index: usize) -> PathBuf {
+    examples_dir(data_dir).join(format!("day{:02}-{}.answers", day.0, index))
+}
+
+// Remove the tags from HTML, keeping all whitespace
+fn text(html: &str) -> String {
+    let mut result = String::with_capacity(html.len());
+    let mut in_tag = false;
+    for c in html.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' => in_tag = false,
+            c if !in_tag => result.push(c),
+            _ => (),
+        }
+    }
+    decode_entities(&result)
+}
+
+// The start and end of each match of `open`...`close` in `html`
+fn find_all(html: &str, open: &str, close: &str) -> Vec<(usize, usize)> {
+    let mut result = Vec::new();
+    let mut pos = 0;
+    while let Some(start) = html[pos..].find(open) {
+        let start = pos + start;
+        let Some(len) = html[start..].find(close) else {
+            break;
+        };
+        pos = start + len + close.len();
+        result.push((start, pos));
+    }
+    result
+}
+
+/// Extract the examples and their expected answers from the articles of a puzzle page
+pub fn extract_examples(articles: &[&str]) -> Vec<(String, Answers)> {
+    let mut examples: Vec<(String, Answers)> = Vec::new();
+    for (part, article) in articles.iter().take(2).enumerate() {
+        let blocks = find_all(article, "<pre><code>", "</code></pre>");
+        // Offsets in the article of the examples added from this article
+        let mut offsets = Vec::new();
+        for &(start, end) in blocks.iter() {
+            let before = &article[..start];
+            let intro = before.rfind("<p>").map_or(before, |i| &before[i..]);
+            if intro.to_lowercase().contains("example") {
+                examples.push((text(&article[start..end]), Answers::default()));
+                offsets.push(start);
+            }
+        }
+        // If no block of this article is introduced as an example, the first one
+        // usually is, unless it repeats an example of part 1
+        if offsets.is_empty() {
+            if let Some(&(start, end)) = blocks.first() {
+                let example = text(&article[start..end]);
+                if examples.iter().all(|(e, _)| *e != example) {
+                    examples.push((example, Answers::default()));
+                    offsets.push(start);
+                }
+            }
+        }
+        // The last answer after each example, and before the next one, belongs
+        // to the example. Without new examples, the answer belongs to the most
+        // recent example of part 1.
+        let answers = find_all(article, "<code><em>", "</em></code>");
+        let first = examples.len() - offsets.len();
+        let segments = if offsets.is_empty() {
+            vec![(first.checked_sub(1), 0, article.len())]
+        } else {
+            let ends = offsets.iter().skip(1).copied().chain([article.len()]);
+            (first..)
+                .map(Some)
+                .zip(offsets.iter().copied())
+                .zip(ends)
+                .map(|((index, start), end)| (index, start, end))
+                .collect()
+        };
+        for (index, start, end) in segments {
+            let answer = answers.iter().rev().find(|&&(a, _)| start <= a && a < end);
+            if let (Some(index), Some(&(a, b))) = (index, answer) {
+                examples[index].1 .0[part] = Some(text(&article[a..b]).trim().to_owned());
+            }
+        }
+    }
+    examples
+}
+
+/// The paths of the stored examples of the day, and of their answers
+pub fn saved_examples(data_dir: &Path, day: Day) -> Vec<(PathBuf, PathBuf)> {
+    (1..)
+        .map(|i| {
+            (
+                example_path(data_dir, day, i),
+                example_answers_path(data_dir, day, i),
+            )
+        })
+        .take_while(|(path, _)| path.exists())
+        .collect()
+}
+
+/// Extract the examples of the day from the downloaded puzzle, replacing any
+/// stored examples. Returns the number of examples and of expected answers.
+pub fn save_examples(data_dir: &Path, day: Day) -> Result<(usize, usize)> {
+    let path = html_path(data_dir, day);
+    let html = match std::fs::read_to_string(&path) {
+        Ok(s) => s,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+            return Err(Error::MissingPuzzle { day, path })
+        }
+        Err(e) => return Err(Error::io(path, e)),
+    };
+    let examples = extract_examples(&extract_articles(&html));
+    let dir = examples_dir(data_dir);
+    std::fs::create_dir_all(&dir).map_err(|e| Error::io(&dir, e))?;
+    for (path, answers_path) in saved_examples(data_dir, day) {
+        std::fs::remove_file(&path).map_err(|e| Error::io(path, e))?;
+        if answers_path.exists() {
+            std::fs::remove_file(&answers_path).map_err(|e| Error::io(answers_path, e))?;
+        }
+    }
+    let mut n_answers = 0;
+    for (i, (example, answers)) in examples.iter().enumerate() {
+        let path = example_path(data_dir, day, i + 1);
+        std::fs::write(&path, example).map_err(|e| Error::io(path, e))?;
+        answers.write(example_answers_path(data_dir, day, i + 1))?;
+        n_answers += answers.0.iter().flatten().count();
+    }
+    Ok((examples.len(), n_answers))
+}
+
+/// The result of solving one part of an example with a known answer
+pub struct ExampleResult {
+    pub day: Day,
+    /// The number of the example of the day, from 1
+    pub index: usize,
+    pub part: u8,
+    pub answer: Answer,
+    pub check: Check,
+}
+
+/// Solve the `parts` of the stored examples which have an expected answer, using
+/// the given solver of each day with the `assignments` of its parameters, like
+/// `runner::solve_days`. Days without a solver are skipped.
+pub fn run_examples(
+    data_dir: &Path,
+    days: &[(Day, Option<&Registration>)],
+    parts: Parts,
+    assignments: &[(String, String)],
+) -> Result<Vec<ExampleResult>> {
+    let mut results = Vec::new();
+    for (&(day, solver), params) in days.iter().zip(day_params(days, assignments)?) {
+        let Some((solver, params)) = solver.zip(params) else {
+            continue;
+        };
+        for (i, (path, answers_path)) in saved_examples(data_dir, day).into_iter().enumerate() {
+            let input = std::fs::read_to_string(&path).map_err(|e| Error::io(path, e))?;
+            let answers = Answers::read(answers_path)?;
+            for part in [1, 2] {
+                if !parts.includes(part) || answers.0[usize::from(part - 1)].is_none() {
+                    continue;
+                }
+                let answer = isolate::catch(|| solver.run_part(&input, part, &params))
+                    .and_then(|answer| Ok(answer?))
+                    .map_err(|failure| Error::failure(day, failure))?;
+                results.push(ExampleResult {
+                    day,
+                    index: i + 1,
+                    part,
+                    check: answers.check(part, &answer),
+                    answer,
+                })
+            }
+        }
+    }
+    Ok(results)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{extract_examples, run_examples};
+    use crate::{
+        answers::{Answers, Check},
+        puzzle::extract_articles,
+        solver::{get_solver, registrations, Parts},
+    };
+    use std::path::Path;
+
+    static PAGE: &str = r#"<main>
+<article class="day-desc"><h2>--- Day 8: Haunted Wasteland ---</h2><p>It seems like you're meant to use the <em>left/right</em> instructions.</p>
+<p>For example:</p>
+<pre><code>RL
+
+AAA = (BBB, CCC)
+ZZZ = (ZZZ, ZZZ)
+</code></pre>
+<p>Starting with <code>AAA</code>, you need to look up the next element. In this example, <code><em>2</em></code> steps are required.</p>
+<p>Of course, you might not find <code>ZZZ</code> right away. Here's another example:</p>
+<pre><code>LLR
+
+AAA = (BBB, BBB)
+</code></pre>
+<p>Starting at <code>AAA</code>, follow the instructions: <code>AAA -&gt; BBB</code>. It takes <code><em>6</em></code> steps to reach <code>ZZZ</code>.</p>
+</article>
+<article class="day-desc"><h2 id="part2">--- Part Two ---</h2><p>For example:</p>
+<pre><code>LR
+
+11A = (11B, XXX)
+</code></pre>
+<p>Here, there are two starting nodes:</p>
+<pre><code>Step 0: You are at <em>11A</em> and 22A.
+</code></pre>
+<p>So, in this example, you end up entirely on nodes that end in <code>Z</code> after <code><em>6</em></code> steps.</p>
+</article>
+</main>"#;
+
+    #[test]
+    fn test_extract() {
+        let examples = extract_examples(&extract_articles(PAGE));
+        let answers =
+            |a: Option<&str>, b: Option<&str>| Answers([a, b].map(|s| s.map(str::to_owned)));
+        assert_eq!(
+            examples,
+            vec![
+                (
+                    "RL\n\nAAA = (BBB, CCC)\nZZZ = (ZZZ, ZZZ)\n".to_owned(),
+                    answers(Some("2"), None)
+                ),
+                (
+                    "LLR\n\nAAA = (BBB, BBB)\n".to_owned(),
+                    answers(Some("6"), None)
+                ),
+                (
+                    "LR\n\n11A = (11B, XXX)\n".to_owned(),
+                    answers(None, Some("6"))
+                ),
+            ]
+        );
+
+        // Part 2 may introduce its own example without calling it one
+        let page = PAGE.replace(
+            "<p>For example:</p>\n<pre><code>LR",
+            "<p>Consider this map:</p>\n<pre><code>LR",
+        );
+        let examples = extract_examples(&extract_articles(&page));
+        assert_eq!(examples.len(), 3);
+        assert_eq!(examples[1].1, answers(Some("6"), None));
+        assert_eq!(
+            examples[2],
+            (
+                "LR\n\n11A = (11B, XXX)\n".to_owned(),
+                answers(None, Some("6"))
+            )
+        );
+
+        // Part 2 may use the example of part 1
+        let page = PAGE.replace(
+            "<p>For example:</p>\n<pre><code>LR\n\n11A = (11B, XXX)",
+            "<p>Again:</p>\n<pre><code>LLR\n\nAAA = (BBB, BBB)",
+        );
+        let examples = extract_examples(&extract_articles(&page));
+        assert_eq!(examples.len(), 2);
+        assert_eq!(examples[1].1, answers(Some("6"), Some("6")));
+    }
+
+    // Run all registered solvers against the examples in the `testdata`
+    // directory of the repository, which is laid out like a data directory
+    #[test]
+    fn test_saved_examples() {
+        let data = Path::new(env!("CARGO_MANIFEST_DIR")).join("testdata");
+        let mut days: Vec<_> = registrations().iter().map(|r| (r.year, r.day)).collect();
+        days.sort_unstable();
+        days.dedup();
+        for (year, day) in days {
+            let days = [(day, get_solver(year, day))];
+            let results =
+                run_examples(&data.join(year.to_string()), &days, Parts::Both, &[]).unwrap();
+            assert!(
+                !results.is_empty(),
+                "{} day {:02} has no examples with expected answers",
+                year,
+                day.0
+            );
+            for result in results {
+                assert!(
+                    result.check == Check::Correct,
+                    "{} day {:02} example {} part {}: got {}, {:?}",
+                    year,
+                    result.day.0,
+                    result.index,
+                    result.part,
+                    result.answer,
+                    result.check
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,423 @@
+// Rectangular grids of cells, as used by most puzzles. Positions are
+// (row, column) pairs counted from the top left, and rows are stored one after
+// another in a single vector.
+
+use std::{
+    fmt::Display,
+    ops::{Index, IndexMut},
+};
+
+use crate::input::{empty_input, Line, ParseError, ParseResult};
+
+/// A position in a grid, as (row, column)
+pub type Pos = (usize, usize);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Direction {
+    North,
+    East,
+    South,
+    West,
+}
+
+impl Direction {
+    /// All directions, clockwise from north
+    pub const ALL: [Direction; 4] = [
+        Direction::North,
+        Direction::East,
+        Direction::South,
+        Direction::West,
+    ];
+
+    pub fn turn_right(self) -> Self {
+        match self {
+            Direction::North => Direction::East,
+            Direction::East => Direction::South,
+            Direction::South => Direction::West,
+            Direction::West => Direction::North,
+        }
+    }
+
+    pub fn turn_left(self) -> Self {
+        self.turn_right().opposite()
+    }
+
+    pub fn opposite(self) -> Self {
+        self.turn_right().turn_right()
+    }
+
+    /// The change in (row, column) of one step in this direction
+    pub fn offset(self) -> (isize, isize) {
+        match self {
+            Direction::North => (-1, 0),
+            Direction::East => (0, 1),
+            Direction::South => (1, 0),
+            Direction::West => (0, -1),
+        }
+    }
+}
+
+// The offsets of the 8 surrounding cells, in reading order
+const OFFSETS_8: [(isize, isize); 8] = [
+    (-1, -1),
+    (-1, 0),
+    (-1, 1),
+    (0, -1),
+    (0, 1),
+    (1, -1),
+    (1, 0),
+    (1, 1),
+];
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    cells: Vec<T>,
+    rows: usize,
+    cols: usize,
+}
+
+// The rows of a grid: the lines of the input up to the last non-blank one, with
+// their whitespace, which may be part of the grid. A blank line before the end
+// is an error, as it would shift the rows after it.
+fn grid_lines(s: &str) -> impl Iterator<Item = ParseResult<Line<'_>>> {
+    let n_rows = s
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .last()
+        .map_or(0, |(i, _)| i + 1);
+    s.lines().take(n_rows).enumerate().map(|(i, text)| {
+        if text.trim().is_empty() {
+            Err(ParseError::new(i + 1, "blank line in grid"))
+        } else {
+            Ok(Line {
+                number: i + 1,
+                text,
+            })
+        }
+    })
+}
+
+/// An error about the cell at `pos` of the grid parsed from `s`
+pub fn error_at(s: &str, pos: Pos, message: impl Into<String>) -> ParseError {
+    match grid_lines(s).nth(pos.0) {
+        Some(Ok(line)) => match line.text.get(pos.1..) {
+            Some(part) => line.error_at(part, message),
+            None => line.error(message),
+        },
+        _ => ParseError::new(1, message),
+    }
+}
+
+impl<T> Grid<T> {
+    /// Make a grid of `rows` rows from the cells in reading order.
+    /// Panics if the number of cells is not a multiple of `rows`.
+    pub fn new(rows: usize, cells: Vec<T>) -> Self {
+        let cols = cells.len().checked_div(rows).unwrap_or(0);
+        assert_eq!(rows * cols, cells.len(), "Cells do not fill {rows} rows");
+        Grid { cells, rows, cols }
+    }
+
+    pub fn filled(rows: usize, cols: usize, value: T) -> Self
+    where
+        T: Clone,
+    {
+        Grid {
+            cells: vec![value; rows * cols],
+            rows,
+            cols,
+        }
+    }
+
+    /// Parse the lines of the input as rows, converting each byte to a cell with
+    /// `f`. Leading and trailing whitespace is part of the rows, and only the end
+    /// of the input may have blank lines. Bytes for which `f` returns None are
+    /// reported as unknown symbols, and all rows must have the same length.
+    pub fn parse(s: &str, mut f: impl FnMut(u8) -> Option<T>) -> ParseResult<Self> {
+        let mut cells = Vec::new();
+        let mut cols = None;
+        let mut rows = 0;
+        for line in grid_lines(s) {
+            let line = line?;
+            let len = line.text.len();
+            match cols {
+                None => cols = Some(len),
+                Some(cols) if cols != len => {
+                    return Err(line.error(format!("row has length {len}, expected {cols}")))
+                }
+                _ => (),
+            }
+            for (i, byte) in line.text.bytes().enumerate() {
+                match f(byte) {
+                    Some(cell) => cells.push(cell),
+                    None => {
+                        // The bytes before are known symbols, so `i` is a character boundary
+                        let at = line.text.get(i..).unwrap_or(line.text);
+                        let symbol = at.chars().next().unwrap_or('?');
+                        return Err(line.error_at(at, format!("unknown symbol '{symbol}'")));
+                    }
+                }
+            }
+            rows += 1;
+        }
+        let cols = cols.ok_or_else(empty_input)?;
+        Ok(Grid { cells, rows, cols })
+    }
+
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    pub fn cols(&self) -> usize {
+        self.cols
+    }
+
+    pub fn contains(&self, pos: Pos) -> bool {
+        pos.0 < self.rows && pos.1 < self.cols
+    }
+
+    pub fn get(&self, pos: Pos) -> Option<&T> {
+        self.contains(pos)
+            .then(|| &self.cells[pos.0 * self.cols + pos.1])
+    }
+
+    pub fn get_mut(&mut self, pos: Pos) -> Option<&mut T> {
+        if self.contains(pos) {
+            Some(&mut self.cells[pos.0 * self.cols + pos.1])
+        } else {
+            None
+        }
+    }
+
+    /// The position `offset` away from `pos`, if it is in the grid
+    pub fn offset(&self, pos: Pos, offset: (isize, isize)) -> Option<Pos> {
+        let row = pos.0.checked_add_signed(offset.0)?;
+        let col = pos.1.checked_add_signed(offset.1)?;
+        self.contains((row, col)).then_some((row, col))
+    }
+
+    /// The position one step from `pos` in the direction, if it is in the grid
+    pub fn step(&self, pos: Pos, direction: Direction) -> Option<Pos> {
+        self.offset(pos, direction.offset())
+    }
+
+    /// The positions north, east, south and west of `pos` that are in the grid
+    pub fn neighbors4(&self, pos: Pos) -> impl Iterator<Item = Pos> + '_ {
+        Direction::ALL
+            .into_iter()
+            .filter_map(move |d| self.step(pos, d))
+    }
+
+    /// The positions surrounding `pos`, including diagonals, that are in the grid
+    pub fn neighbors8(&self, pos: Pos) -> impl Iterator<Item = Pos> + '_ {
+        OFFSETS_8
+            .into_iter()
+            .filter_map(move |offset| self.offset(pos, offset))
+    }
+
+    /// All positions in reading order
+    pub fn positions(&self) -> impl Iterator<Item = Pos> {
+        let cols = self.cols;
+        (0..self.rows).flat_map(move |row| (0..cols).map(move |col| (row, col)))
+    }
+
+    /// All cells with their positions, in reading order
+    pub fn iter(&self) -> impl Iterator<Item = (Pos, &T)> {
+        self.positions().zip(self.cells.iter())
+    }
+
+    /// The position of the first cell in reading order matching `predicate`
+    pub fn find(&self, mut predicate: impl FnMut(&T) -> bool) -> Option<Pos> {
+        self.iter()
+            .find_map(|(pos, cell)| predicate(cell).then_some(pos))
+    }
+
+    pub fn row(&self, row: usize) -> &[T] {
+        &self.cells[row * self.cols..(row + 1) * self.cols]
+    }
+
+    pub fn iter_rows(&self) -> impl Iterator<Item = &[T]> {
+        // Grids without columns still have their rows
+        (0..self.rows).map(|row| self.row(row))
+    }
+
+    pub fn column(&self, col: usize) -> impl Iterator<Item = &T> {
+        assert!(col < self.cols, "Column {col} out of bounds");
+        self.cells.iter().skip(col).step_by(self.cols)
+    }
+
+    pub fn iter_columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        (0..self.cols).map(|col| self.column(col))
+    }
+
+    /// Make a grid with the cells converted by `f`
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            cells: self.cells.iter().map(f).collect(),
+            rows: self.rows,
+            cols: self.cols,
+        }
+    }
+
+    /// Render the grid with one line per row, converting each cell to a character
+    pub fn render(&self, mut f: impl FnMut(&T) -> char) -> String {
+        let mut s = String::with_capacity(self.rows * (self.cols + 1));
+        for row in self.iter_rows() {
+            s.extend(row.iter().map(&mut f));
+            s.push('\n');
+        }
+        s
+    }
+}
+
+impl<T: Clone> Grid<T> {
+    /// Swap rows and columns
+    pub fn transpose(&self) -> Self {
+        let cells = (0..self.cols)
+            .flat_map(|col| self.column(col).cloned())
+            .collect();
+        Grid {
+            cells,
+            rows: self.cols,
+            cols: self.rows,
+        }
+    }
+
+    /// Rotate a quarter turn clockwise
+    pub fn rotate_right(&self) -> Self {
+        let cells = (0..self.cols)
+            .flat_map(|col| {
+                let column: Vec<_> = self.column(col).cloned().collect();
+                column.into_iter().rev()
+            })
+            .collect();
+        Grid {
+            cells,
+            rows: self.cols,
+            cols: self.rows,
+        }
+    }
+
+    /// Rotate a quarter turn counterclockwise
+    pub fn rotate_left(&self) -> Self {
+        let cells = (0..self.cols)
+            .rev()
+            .flat_map(|col| self.column(col).cloned())
+            .collect();
+        Grid {
+            cells,
+            rows: self.cols,
+            cols: self.rows,
+        }
+    }
+}
+
+impl<T> Index<Pos> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, pos: Pos) -> &T {
+        assert!(self.contains(pos), "Position {pos:?} out of bounds");
+        &self.cells[pos.0 * self.cols + pos.1]
+    }
+}
+
+impl<T> IndexMut<Pos> for Grid<T> {
+    fn index_mut(&mut self, pos: Pos) -> &mut T {
+        assert!(self.contains(pos), "Position {pos:?} out of bounds");
+        &mut self.cells[pos.0 * self.cols + pos.1]
+    }
+}
+
+impl<T: Display> Display for Grid<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for row in self.iter_rows() {
+            for cell in row {
+                write!(f, "{cell}")?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Direction, Grid};
+    use crate::input::dedent;
+
+    static TEST_STR: &str = "ab.
+    .cd";
+
+    fn grid() -> Grid<char> {
+        Grid::parse(&dedent(TEST_STR), |b| Some(b as char)).unwrap()
+    }
+
+    #[test]
+    fn test_parse() {
+        let grid = grid();
+        assert_eq!((grid.rows(), grid.cols()), (2, 3));
+        assert_eq!(grid[(1, 2)], 'd');
+        assert_eq!(grid.get((2, 0)), None);
+        assert_eq!(grid.to_string(), "ab.\n.cd\n");
+        assert_eq!(grid.render(|&c| c.to_ascii_uppercase()), "AB.\n.CD\n");
+        let error = |s: &str| {
+            Grid::parse(s, |b| (b != b'x').then_some(b))
+                .unwrap_err()
+                .to_string()
+        };
+        assert_eq!(
+            error("ab.\n.c"),
+            "input error at line 2: row has length 2, expected 3"
+        );
+        assert_eq!(
+            error("ab.\n.xd"),
+            "input error at line 2, column 2: unknown symbol 'x'"
+        );
+        // Leading whitespace is part of the row, and counts for columns
+        assert_eq!(
+            error("ab.\n  .c"),
+            "input error at line 2: row has length 4, expected 3"
+        );
+        // Blank lines may only follow the grid
+        assert_eq!(
+            error("ab.\n  \n.cd\n"),
+            "input error at line 2: blank line in grid"
+        );
+        let trailing = Grid::parse("ab\ncd\n\n  \n", Some).unwrap();
+        assert_eq!((trailing.rows(), trailing.cols()), (2, 2));
+        let spaced = Grid::parse("  #.\n#..#\n", Some).unwrap();
+        assert_eq!(spaced.render(|&b| b as char), "  #.\n#..#\n");
+        assert_eq!(
+            error(" .x\n...\n"),
+            "input error at line 1, column 3: unknown symbol 'x'"
+        );
+        assert_eq!(error("\n"), "input error at line 1: the input is empty");
+    }
+
+    #[test]
+    fn test_neighbors() {
+        let grid = grid();
+        let v: Vec<_> = grid.neighbors4((0, 0)).collect();
+        assert_eq!(v, [(0, 1), (1, 0)]);
+        let v: Vec<_> = grid.neighbors8((1, 1)).collect();
+        assert_eq!(v, [(0, 0), (0, 1), (0, 2), (1, 0), (1, 2)]);
+        assert_eq!(grid.step((1, 2), Direction::East), None);
+        assert_eq!(grid.step((1, 2), Direction::North), Some((0, 2)));
+        assert_eq!(grid.find(|&c| c == 'c'), Some((1, 1)));
+        assert_eq!(Direction::North.turn_right(), Direction::East);
+        assert_eq!(Direction::North.turn_left(), Direction::West);
+        assert_eq!(Direction::East.opposite(), Direction::West);
+    }
+
+    #[test]
+    fn test_transform() {
+        let grid = grid();
+        let column: String = grid.column(1).collect();
+        assert_eq!(column, "bc");
+        assert_eq!(grid.iter_rows().nth(1), Some(&['.', 'c', 'd'][..]));
+        assert_eq!(grid.transpose().to_string(), "a.\nbc\n.d\n");
+        assert_eq!(grid.rotate_right().to_string(), ".a\ncb\nd.\n");
+        assert_eq!(grid.rotate_left().to_string(), ".d\nbc\na.\n");
+        assert_eq!(grid.rotate_right().rotate_left(), grid);
+    }
+}
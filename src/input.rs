@@ -0,0 +1,270 @@
+// Helpers for parsing puzzle inputs, which report the position of any error.
+// Inputs are normalized and prepared for the layout of the day before parsing,
+// see `normalize` and `Layout`.
+
+use std::{fmt::Display, str::FromStr};
+
+/// An error in the input of a day. Lines and columns are counted from 1.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub line: usize,
+    pub column: Option<usize>,
+    pub message: String,
+}
+
+pub type ParseResult<T> = Result<T, ParseError>;
+
+impl ParseError {
+    pub fn new(line: usize, message: impl Into<String>) -> Self {
+        ParseError {
+            line,
+            column: None,
+            message: message.into(),
+        }
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "input error at line {}", self.line)?;
+        if let Some(column) = self.column {
+            write!(f, ", column {column}")?;
+        }
+        write!(f, ": {}", self.message)
+    }
+}
+
+/// Prepare an input for parsing: a byte order mark is removed, CRLF line
+/// endings become LF, and the input ends with a single newline, unless it is
+/// empty. Whitespace within and at the start of lines is kept, and so are line
+/// numbers, so errors point at the original input.
+pub fn normalize(s: &str) -> String {
+    let s = s.strip_prefix('\u{feff}').unwrap_or(s);
+    let mut result = String::with_capacity(s.len() + 1);
+    // `lines` also removes the CR of CRLF line endings
+    for line in s.lines() {
+        result.push_str(line);
+        result.push('\n');
+    }
+    // Remove trailing blank lines, but not the trailing spaces of a last line
+    // that has other content
+    let end = result.trim_end().len();
+    let end = result[end..].find('\n').map_or(result.len(), |i| end + i);
+    result.truncate(end);
+    if result.trim().is_empty() {
+        result.clear();
+    } else {
+        result.push('\n');
+    }
+    result
+}
+
+/// How the input of a day is laid out, as declared by `Solver::LAYOUT`. The
+/// input is prepared for its layout before parsing, keeping line numbers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Layout {
+    /// Lines whose surrounding whitespace carries no meaning, read with `lines`.
+    /// Each line is trimmed.
+    #[default]
+    Lines,
+    /// Sections separated by blank lines, read with `blocks`. Lines keep their
+    /// whitespace, and blank lines become empty.
+    Blocks,
+    /// A map of characters, read with `grid::Grid::parse`. Lines keep their
+    /// whitespace, and the map must be rectangular, without blank lines within.
+    Grid,
+}
+
+impl Layout {
+    /// Normalize the input, then prepare it for this layout, or report where
+    /// it does not fit
+    pub fn prepare(self, s: &str) -> ParseResult<String> {
+        let s = normalize(s);
+        let map_lines =
+            |f: fn(&str) -> &str| s.lines().map(|line| f(line).to_owned() + "\n").collect();
+        match self {
+            Layout::Lines => Ok(map_lines(str::trim)),
+            Layout::Blocks => Ok(map_lines(
+                |line| if line.trim().is_empty() { "" } else { line },
+            )),
+            Layout::Grid => {
+                crate::grid::Grid::parse(&s, |_| Some(()))?;
+                Ok(s)
+            }
+        }
+    }
+}
+
+/// Remove the indentation of a test literal, whose first line follows the
+/// opening quote, and whose other lines are indented like the code around it.
+/// The indentation that the non-blank lines after the first have in common is
+/// removed, and blank lines become empty. Tests call this on indented literals
+/// of days whose leading whitespace matters, like grids and blocks.
+pub fn dedent(s: &str) -> String {
+    let indentation = |line: &str| line.len() - line.trim_start_matches([' ', '\t']).len();
+    let common = s
+        .lines()
+        .skip(1)
+        .filter(|line| !line.trim().is_empty())
+        .map(indentation)
+        .min()
+        .unwrap_or(0);
+    let mut result = String::with_capacity(s.len());
+    for (i, line) in s.lines().enumerate() {
+        let line = if line.trim().is_empty() {
+            ""
+        } else if i == 0 {
+            line
+        } else {
+            &line[common..]
+        };
+        result.push_str(line);
+        result.push('\n');
+    }
+    result
+}
+
+/// A non-empty line of the input. Lines from `lines` are trimmed, lines from
+/// `blocks` are kept as they are.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Line<'a> {
+    pub number: usize,
+    pub text: &'a str,
+}
+
+/// The non-empty lines of the input, with surrounding whitespace removed
+pub fn lines(s: &str) -> impl Iterator<Item = Line<'_>> {
+    s.lines().enumerate().filter_map(|(i, line)| {
+        let text = line.trim();
+        (!text.is_empty()).then_some(Line {
+            number: i + 1,
+            text,
+        })
+    })
+}
+
+/// The blocks of consecutive non-blank lines, which are separated by blank
+/// lines. The whitespace of the lines is kept.
+pub fn blocks(s: &str) -> impl Iterator<Item = Vec<Line<'_>>> {
+    let mut lines = s.lines().enumerate().peekable();
+    std::iter::from_fn(move || {
+        // Skip the blank lines before the block
+        while lines.next_if(|(_, line)| line.trim().is_empty()).is_some() {}
+        let mut block = Vec::new();
+        while let Some((i, text)) = lines.next_if(|(_, line)| !line.trim().is_empty()) {
+            block.push(Line {
+                number: i + 1,
+                text,
+            });
+        }
+        (!block.is_empty()).then_some(block)
+    })
+}
+
+impl<'a> Line<'a> {
+    /// An error about the whole line
+    pub fn error(&self, message: impl Into<String>) -> ParseError {
+        ParseError::new(self.number, message)
+    }
+
+    /// An error about `part`, which must be a slice of the text of this line
+    pub fn error_at(&self, part: &str, message: impl Into<String>) -> ParseError {
+        let offset = (part.as_ptr() as usize).wrapping_sub(self.text.as_ptr() as usize);
+        ParseError {
+            column: (offset <= self.text.len()).then_some(offset + 1),
+            ..self.error(message)
+        }
+    }
+
+    /// Parse `part` of this line, e.g. "invalid number 'x'" if `what` is "number"
+    pub fn parse<T: FromStr>(&self, part: &str, what: &str) -> ParseResult<T> {
+        part.parse()
+            .map_err(|_| self.error_at(part, format!("invalid {what} '{part}'")))
+    }
+
+    /// Split `part` of this line at the first `delimiter`
+    pub fn split_once(&self, part: &'a str, delimiter: &str) -> ParseResult<(&'a str, &'a str)> {
+        part.split_once(delimiter)
+            .ok_or_else(|| self.error_at(part, format!("expected '{delimiter}'")))
+    }
+}
+
+/// The error if the input has no lines
+pub fn empty_input() -> ParseError {
+    ParseError::new(1, "the input is empty")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{blocks, dedent, lines, normalize, Layout, ParseError};
+
+    #[test]
+    fn test_lines() {
+        let v: Vec<_> = lines("a 1\n\n  b x \n").collect();
+        assert_eq!(v.len(), 2);
+        assert_eq!((v[1].number, v[1].text), (3, "b x"));
+        let (name, n) = v[1].split_once(v[1].text, " ").unwrap();
+        assert_eq!(name, "b");
+        assert_eq!(
+            v[1].parse::<u8>(n, "number"),
+            Err(ParseError {
+                line: 3,
+                column: Some(3),
+                message: "invalid number 'x'".to_owned()
+            })
+        );
+        assert_eq!(
+            v[0].split_once(v[0].text, ":").unwrap_err().to_string(),
+            "input error at line 1, column 1: expected ':'"
+        );
+        assert_eq!(v[0].error("bad").to_string(), "input error at line 1: bad");
+    }
+
+    #[test]
+    fn test_normalize() {
+        assert_eq!(normalize("\u{feff}a b \r\n  c\r\n\r\n \n"), "a b \n  c\n");
+        assert_eq!(normalize("#..\n .#\n ..  "), "#..\n .#\n ..  \n");
+        assert_eq!(normalize(" \n\n"), "");
+        assert_eq!(normalize(""), "");
+        let blocks = "header\n  a\n  b\n";
+        assert_eq!(normalize(blocks), blocks);
+    }
+
+    #[test]
+    fn test_layout() {
+        let s = "\u{feff} a  b \r\n \r\n  c\r\n\r\n";
+        assert_eq!(Layout::Lines.prepare(s), Ok("a  b\n\nc\n".to_owned()));
+        assert_eq!(Layout::Blocks.prepare(s), Ok(" a  b \n\n  c\n".to_owned()));
+        assert_eq!(
+            Layout::Grid.prepare(s).unwrap_err().to_string(),
+            "input error at line 2: blank line in grid"
+        );
+        assert_eq!(
+            Layout::Grid.prepare(" #.\r\n.# \n"),
+            Ok(" #.\n.# \n".to_owned())
+        );
+    }
+
+    #[test]
+    fn test_dedent() {
+        assert_eq!(dedent("x\n    a\n  \n      b"), "x\na\n\n  b\n");
+        assert_eq!(dedent("#.\n    .#"), "#.\n.#\n");
+        // Literals without indentation are kept
+        assert_eq!(dedent("#..\n.#.\n"), "#..\n.#.\n");
+    }
+
+    #[test]
+    fn test_blocks() {
+        let s = "seeds: 1\n\n \nmap:\n  1 2\n3 4\n";
+        let v: Vec<Vec<_>> = blocks(s)
+            .map(|block| block.iter().map(|l| (l.number, l.text)).collect())
+            .collect();
+        assert_eq!(
+            v,
+            [
+                vec![(1, "seeds: 1")],
+                vec![(4, "map:"), (5, "  1 2"), (6, "3 4")]
+            ]
+        );
+    }
+}
@@ -0,0 +1,117 @@
+// Running solvers such that a panic or an endless loop in one day is reported
+// as a failure of that day, instead of ending or hanging the whole run.
+
+use std::{
+    cell::{Cell, RefCell},
+    panic::{self, AssertUnwindSafe, PanicHookInfo},
+    sync::{mpsc, Once},
+    thread,
+    time::Duration,
+};
+
+use crate::solver::Failure;
+
+thread_local! {
+    // Whether panics on this thread are caught by `catch`
+    static CATCHING: Cell<bool> = const { Cell::new(false) };
+    // The message and location of the last caught panic on this thread
+    static CAUGHT: RefCell<Option<String>> = const { RefCell::new(None) };
+}
+
+static INSTALL_HOOK: Once = Once::new();
+
+// The payload only holds the message, so the location is taken in a panic hook,
+// which also keeps caught panics from being printed. Other panics are printed
+// by the previous hook as usual.
+fn install_hook() {
+    INSTALL_HOOK.call_once(|| {
+        let previous = panic::take_hook();
+        panic::set_hook(Box::new(move |info| {
+            if CATCHING.get() {
+                CAUGHT.set(Some(describe(info)))
+            } else {
+                previous(info)
+            }
+        }))
+    })
+}
+
+fn describe(info: &PanicHookInfo) -> String {
+    let payload = info.payload();
+    let message = payload
+        .downcast_ref::<&str>()
+        .copied()
+        .or_else(|| payload.downcast_ref::<String>().map(String::as_str))
+        .unwrap_or("Box<dyn Any>");
+    match info.location() {
+        Some(location) => format!("{message} at {}:{}", location.file(), location.line()),
+        None => message.to_owned(),
+    }
+}
+
+/// Call `f`, reporting a panic as `Failure::Panicked` with its message and location
+pub fn catch<R>(f: impl FnOnce() -> R) -> Result<R, Failure> {
+    install_hook();
+    let was_catching = CATCHING.replace(true);
+    let result = panic::catch_unwind(AssertUnwindSafe(f));
+    CATCHING.set(was_catching);
+    result
+        .map_err(|_| Failure::Panicked(CAUGHT.take().unwrap_or_else(|| "unknown panic".to_owned())))
+}
+
+/// Like `catch`, but on another thread, which is given up on after `timeout`.
+/// Threads cannot be stopped, so a thread that times out runs on in the
+/// background until the process exits.
+pub fn catch_with_timeout<R: Send + 'static>(
+    f: impl FnOnce() -> R + Send + 'static,
+    timeout: Duration,
+) -> Result<R, Failure> {
+    let (sender, receiver) = mpsc::channel();
+    thread::spawn(move || {
+        // The receiver is gone if we timed out
+        let _ = sender.send(catch(f));
+    });
+    receiver
+        .recv_timeout(timeout)
+        .unwrap_or(Err(Failure::TimedOut(timeout)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{catch, catch_with_timeout};
+    use crate::solver::Failure;
+    use std::time::Duration;
+
+    #[test]
+    fn test_catch() {
+        assert_eq!(catch(|| 1), Ok(1));
+        let line = line!() + 1;
+        let result = catch(|| -> u8 { panic!("no node {}", "AAA") });
+        assert_eq!(
+            result,
+            Err(Failure::Panicked(format!(
+                "no node AAA at {}:{line}",
+                file!()
+            )))
+        );
+        let line = line!() + 1;
+        let result = catch(|| -> u8 { std::panic::panic_any(5) });
+        assert_eq!(
+            result,
+            Err(Failure::Panicked(format!(
+                "Box<dyn Any> at {}:{line}",
+                file!()
+            )))
+        );
+    }
+
+    #[test]
+    fn test_timeout() {
+        let timeout = Duration::from_millis(50);
+        assert_eq!(catch_with_timeout(|| 1, Duration::from_secs(10)), Ok(1));
+        let result = catch_with_timeout(|| std::thread::sleep(Duration::from_secs(60)), timeout);
+        assert_eq!(result, Err(Failure::TimedOut(timeout)));
+        let result = catch_with_timeout(|| -> u8 { unreachable!() }, timeout);
+        assert!(matches!(result, Err(Failure::Panicked(_))));
+    }
+}
@@ -0,0 +1,72 @@
+//! Solutions to Advent of Code, and the tools around them: downloading inputs
+//! and puzzles, solving and timing days, checking and submitting answers.
+//!
+//! Each day is a unit struct implementing [`Solver`], which can be used directly:
+//!
+//! ```
+//! use advent2023::{days::day01::Day01, Solver};
+//!
+//! let input = "1abc2\npqr3stu8vwx\na1b2c3d4e5f\ntreb7uchet\n";
+//! let (part1, part2) = Day01::solve(input)?;
+//! assert_eq!((part1?, part2), (142, 142));
+//! # Ok::<(), advent2023::input::ParseError>(())
+//! ```
+//!
+//! The [`runner`] finds the solver of a day and solves the input stored in a
+//! data directory, and [`download`] fetches inputs from the Advent of Code website.
+//! Fallible functions return [`Result`], whose [`Error`] maps to the exit codes
+//! of the command line tool.
+
+pub mod answer;
+pub mod answers;
+pub mod client;
+pub mod config;
+pub mod days;
+pub mod download;
+pub mod error;
+pub mod examples;
+pub mod grid;
+pub mod input;
+mod isolate;
+pub mod output;
+mod parallel;
+pub mod params;
+pub mod puzzle;
+pub mod runner;
+pub mod selection;
+pub mod solver;
+pub mod submit;
+#[cfg(test)]
+mod testserver;
+pub mod visualize;
+pub mod year;
+
+use std::str::FromStr;
+
+pub use answer::Answer;
+pub use error::{Error, Result};
+pub use solver::Solver;
+pub use year::Year;
+
+/// A day of the event, from 1 to 25
+///
+/// ```
+/// use advent2023::Day;
+///
+/// assert_eq!("7".parse::<Day>()?, Day(7));
+/// assert!("26".parse::<Day>().is_err());
+/// # Ok::<(), advent2023::Error>(())
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Day(pub u8);
+
+impl FromStr for Day {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.parse::<u8>() {
+            Ok(n) if (1..=25).contains(&n) => Ok(Day(n)),
+            _ => Err(Error::BadDay(s.to_owned())),
+        }
+    }
+}
@@ -1,307 +1,711 @@
-// TODO: Add a proper error framework - anyhow?
+// The command line interface. The work is done by the library; this only parses
+// arguments and prints the results.
 
-mod days;
+mod bench;
+mod watch;
 
 use std::{
     cell::OnceCell,
-    fmt::Display,
-    io::ErrorKind,
+    io::IsTerminal,
     path::{Path, PathBuf},
-    time::Duration,
+    process::ExitCode,
+    time::{Duration, SystemTime},
 };
 
-#[allow(unused)]
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-struct Unimplemented;
+use advent2023::{
+    answer::Answer,
+    answers::Check,
+    client::AocClient,
+    config::{parse_seconds, Config},
+    download,
+    error::{Error, Result},
+    examples::{self, ExampleResult},
+    output::{self, DayReport, Format},
+    params::parse_assignment,
+    puzzle,
+    runner::{self, InputSource},
+    selection,
+    solver::{self, get_solver, get_variant, registrations, Failure, Parts, Registration},
+    submit,
+    visualize::Picture,
+    year::{Year, DEFAULT_YEAR},
+    Day,
+};
+
+use clap::{self, Args, Parser, Subcommand};
 
-impl Display for Unimplemented {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        f.write_str("< Part two not yet implemented! >")
+fn get_days(
+    day_strings: Option<Vec<String>>,
+    all: bool,
+    year: Year,
+    data_dir: Option<&Path>,
+) -> Result<Vec<Day>> {
+    // Parse the day strings into a list of days
+    if all {
+        if day_strings.is_some() {
+            return Err(Error::Usage(
+                "If --all days is set, individual days cannot be listed",
+            ));
+        }
+        Ok((1..=year.days()).map(Day).collect::<Vec<_>>())
+    } else if let Some(v) = day_strings {
+        selection::select_days(&v, year, data_dir)
+    } else {
+        Err(Error::Usage("No days chosen"))
     }
 }
 
-use clap::{self, Parser, Subcommand};
-use reqwest::blocking::Client;
-
-type TimedBoxes = (Duration, Box<dyn Display>, Box<dyn Display>);
-type BoxedFn = Box<dyn Fn(&str) -> (Box<dyn Display>, Box<dyn Display>)>;
-
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
-struct Day(u8);
-
-// TODO: Proper error
-impl Day {
-    fn from_str(s: &str) -> Self {
-        match s.parse::<u8>() {
-            Err(_) => {
-                eprintln!("Error: Cannot parse \"{s}\" as integer in 1-25.");
-                std::process::exit(1);
-            }
-            Ok(n) => {
-                if !(1..=25).contains(&n) {
-                    eprintln!("Error: Day {n} not in 1-25.");
-                    std::process::exit(1);
-                }
-                Day(n)
-            }
+// The data directory of the year, after moving data of the old flat layout
+// into it. Only commands that read the data directory call this. If the data
+// cannot be moved, the flat layout is used as is.
+fn year_data_dir(data_dir: &Path, year: Year) -> PathBuf {
+    match year.migrate_flat_layout(data_dir) {
+        Ok(0) => year.data_dir(data_dir),
+        Ok(n) => {
+            let dir = year.data_dir(data_dir);
+            eprintln!("Moved {n} file(s) of {year} from {data_dir:?} to {dir:?}");
+            dir
+        }
+        Err(e) => {
+            eprintln!(
+                "Could not move the data of {year} to {:?}, using {data_dir:?}: {e}",
+                year.data_dir(data_dir)
+            );
+            data_dir.to_owned()
         }
     }
 }
 
-fn parse_days<T: AsRef<str>>(v: &[T]) -> Vec<Day> {
-    let mut days: Vec<_> = v.iter().map(|i| Day::from_str(i.as_ref())).collect();
-    days.sort_unstable();
-    days.dedup();
-    days
+/// Options of `solve` that don't select the days
+#[derive(Args)]
+struct SolveOptions {
+    /// Use this variant of each day's solver instead of the default
+    #[arg(long)]
+    variant: Option<String>,
+    #[arg(long, value_enum, default_value_t)]
+    format: Format,
+    /// Compare the answers to the stored answers in the data directory
+    #[arg(long)]
+    check: bool,
+    /// Show the time spent parsing and solving each part, and the total of all days
+    #[arg(long)]
+    timings: bool,
+    /// Number of days to solve in parallel. 0 means one per CPU
+    #[arg(long, short, default_value_t = 1)]
+    jobs: usize,
+    /// Set a parameter of the days, e.g. `--param expansion2=10`. See the `params` command
+    #[arg(long = "param", value_name = "NAME=VALUE", value_parser = parse_assignment)]
+    params: Vec<(String, String)>,
+    /// Solve only this part (1 or 2) of each day
+    #[arg(long, value_parser = clap::value_parser!(u8).range(1..=2))]
+    part: Option<u8>,
+    /// Give up on a day whose solver takes longer than this many seconds
+    #[arg(long, value_parser = parse_seconds)]
+    timeout: Option<Duration>,
+    /// Read the input of the selected day from this file instead of the data directory.
+    /// All arguments are then days
+    #[arg(long, conflicts_with = "input_pattern")]
+    input: Option<PathBuf>,
+    /// Read the input of each day from a path like 'inputs/{day:02}.txt' instead
+    /// of the data directory. {day} is replaced by the day without padding. All
+    /// arguments are then days
+    #[arg(long)]
+    input_pattern: Option<String>,
+    /// The data directory, when reading inputs with --input or --input-pattern,
+    /// e.g. to --check against its answers
+    #[arg(long = "data-dir", value_name = "DATA_DIR")]
+    explicit_data_dir: Option<PathBuf>,
+    /// Solve again whenever an input, or an example of the days, changes. The
+    /// examples are solved first, and the inputs only if the examples pass
+    #[arg(long)]
+    watch: bool,
 }
 
-// TODO: Handle errors
-fn load_days<'a, T>(
-    dir: &Path,
-    days_and_functions: &'a [(Day, Option<T>)],
-) -> Vec<(Day, Option<(&'a T, String)>)> {
-    days_and_functions
-        .iter()
-        .map(|(d, f)| {
-            (
-                *d,
-                f.as_ref().map(|f| {
-                    let path = &dir.join(format!("day{:02}.txt", d.0));
-                    let string = match std::fs::read_to_string(path) {
-                        Ok(s) => s,
-                        Err(e) => {
-                            if e.kind() == ErrorKind::NotFound {
-                                eprintln!("Data file not found at path {:?}", path);
-                                std::process::exit(1)
-                            } else {
-                                eprint!("Error when reading file:\n{}", e);
-                                std::process::exit(1);
-                            }
-                        }
-                    };
-                    (f, string)
-                }),
-            )
-        })
-        .collect()
+// Where to read the inputs from, and the data directory of the year if there is
+// one. Without --input or --input-pattern, the first argument is the data
+// directory, or "-" to read standard input. With them, all arguments are days,
+// and the data directory may be given with --data-dir.
+fn input_source(
+    first_arg: Option<PathBuf>,
+    year: Year,
+    day_strings: Option<Vec<String>>,
+    options: &SolveOptions,
+) -> Result<(InputSource, Option<PathBuf>, Option<Vec<String>>)> {
+    let source = match (&options.input, &options.input_pattern) {
+        (Some(path), _) => Some(InputSource::File(path.clone())),
+        (None, Some(pattern)) => Some(InputSource::Pattern(pattern.clone())),
+        (None, None) => None,
+    };
+    let Some(source) = source else {
+        if options.explicit_data_dir.is_some() {
+            return Err(Error::Usage(
+                "--data-dir is only used with --input or --input-pattern; \
+                 otherwise give the data directory before the days",
+            ));
+        }
+        return match first_arg {
+            Some(path) if path.as_os_str() == "-" => Ok((InputSource::Stdin, None, day_strings)),
+            Some(path) => {
+                let data_dir = year_data_dir(&path, year);
+                Ok((
+                    InputSource::DataDir(data_dir.clone()),
+                    Some(data_dir),
+                    day_strings,
+                ))
+            }
+            None => Err(Error::Usage("No data directory given")),
+        };
+    };
+    let day_strings = match (first_arg, day_strings) {
+        (None, v) => v,
+        (Some(first), v) => {
+            let mut days = vec![first.to_string_lossy().into_owned()];
+            days.extend(v.into_iter().flatten());
+            Some(days)
+        }
+    };
+    let data_dir = options
+        .explicit_data_dir
+        .as_ref()
+        .map(|dir| year.data_dir(dir));
+    Ok((source, data_dir, day_strings))
 }
 
-fn wrapper<F, R1, R2>(f: F) -> Option<BoxedFn>
-where
-    R1: Display + 'static,
-    R2: Display + 'static,
-    F: Fn(&str) -> (R1, R2) + 'static,
-{
-    Some(Box::new(move |s| {
-        let (a, b) = f(s);
-        (Box::new(a), Box::new(b))
-    }))
+/// A `solve` run with its days and options resolved, which `--watch` repeats
+struct SolveRun {
+    source: InputSource,
+    /// The data directory of the year, or None if the inputs are read from elsewhere
+    data_dir: Option<PathBuf>,
+    days: Vec<(Day, Option<&'static Registration>)>,
+    format: Format,
+    check: bool,
+    timings: bool,
+    parts: Parts,
+    /// The `--param` assignments
+    params: Vec<(String, String)>,
+    jobs: usize,
+    timeout: Option<Duration>,
 }
 
-fn get_solver(day: Day) -> Option<BoxedFn> {
-    match day {
-        Day(1) => wrapper(days::day01::solve),
-        Day(2) => wrapper(days::day02::solve),
-        Day(3) => wrapper(days::day03::solve),
-        Day(4) => wrapper(days::day04::solve),
-        Day(5) => wrapper(days::day05::solve),
-        Day(6) => wrapper(days::day06::solve),
-        Day(7) => wrapper(days::day07::solve),
-        Day(8) => wrapper(days::day08::solve),
-        Day(9) => wrapper(days::day09::solve),
-        Day(10) => wrapper(days::day10::solve),
-        Day(11) => wrapper(days::day11::solve),
-        _ => None,
-    }
-}
+impl SolveRun {
+    /// Solve and check the days. Returns the reports, their rendering, and the
+    /// number of wrong answers.
+    fn run(&self) -> Result<(Vec<DayReport>, String, usize)> {
+        let mut reports = runner::solve_days(
+            &self.source,
+            &self.days,
+            self.parts,
+            &self.params,
+            self.jobs,
+            self.timeout,
+        )?;
 
-fn print_solution(day: Day, solution: Option<TimedBoxes>) {
-    print!("Day {:02}", day.0);
-    if let Some((duration, a, b)) = solution {
-        println!(" [{:.2?}]:\n  Part 1: {}\n  Part 2: {}\n", duration, a, b)
-    } else {
-        println!(":\n  Unimplemented!\n")
+        // Compare the answers of implemented days to the stored answers
+        let n_wrong = match &self.data_dir {
+            Some(data_dir) if self.check => runner::check_reports(data_dir, &mut reports)?,
+            _ => 0,
+        };
+        let output = output::render(self.format, &reports, self.check, self.timings);
+        Ok((reports, output, n_wrong))
     }
 }
 
-fn get_days(day_strings: Option<Vec<String>>, all: bool) -> Vec<Day> {
-    // Parse the day strings into a list of days
-    if all {
-        if day_strings.is_some() {
-            eprintln!("If --all days is set, individual days cannot be listed");
-            std::process::exit(1);
+// The error to exit with after printing the reports, if any
+fn solve_outcome(reports: &[DayReport], n_wrong: usize) -> Result<()> {
+    let (mut n_bad_inputs, mut n_failures) = (0, 0);
+    for report in reports {
+        match report.solution {
+            Some(Err(Failure::BadInput(_))) => n_bad_inputs += 1,
+            Some(Err(_)) => n_failures += 1,
+            Some(Ok(ref solution)) if solution.error().is_some() => n_bad_inputs += 1,
+            _ => (),
         }
-        (1..=25).map(Day).collect::<Vec<_>>()
-    } else if let Some(v) = day_strings {
-        parse_days(&v)
-    } else {
-        eprintln!("No days chosen");
-        std::process::exit(1);
     }
+    if n_failures > 0 {
+        return Err(Error::SolverFailures(n_failures));
+    }
+    if n_bad_inputs > 0 {
+        return Err(Error::BadInputs(n_bad_inputs));
+    }
+    if n_wrong > 0 {
+        return Err(Error::WrongAnswers(n_wrong));
+    }
+    Ok(())
 }
 
-fn solve(data_dir: &Path, day_strings: Option<Vec<String>>, all: bool) {
+fn solve(
+    first_arg: Option<PathBuf>,
+    year: Year,
+    day_strings: Option<Vec<String>>,
+    all: bool,
+    options: SolveOptions,
+) -> Result<()> {
+    let (source, data_dir, day_strings) = input_source(first_arg, year, day_strings, &options)?;
+    let SolveOptions {
+        variant,
+        format,
+        check,
+        timings,
+        jobs,
+        params,
+        part,
+        timeout,
+        watch,
+        ..
+    } = options;
+    if check && data_dir.is_none() {
+        return Err(Error::Usage(
+            "--check compares to the answers in the data directory, so it must be given",
+        ));
+    }
+
     // Parse the day strings into a list of days
-    let days = get_days(day_strings, all);
+    let days = get_days(day_strings, all, year, data_dir.as_deref())?;
 
-    // Get the functions corresponding to the days, or None if the functions
+    // Get the solvers corresponding to the days, or None if the solvers
     // have not been implemented
-    let mut days_and_functions: Vec<_> = days.iter().map(|d| (*d, get_solver(*d))).collect();
+    let mut days_and_functions: Vec<_> = days
+        .iter()
+        .map(|&d| {
+            let solver = match &variant {
+                None => get_solver(year, d),
+                Some(v) => get_variant(year, d, v),
+            };
+            (d, solver)
+        })
+        .collect();
 
     // If --all is picked, remove days and functions that are unimplemented,
-    // such that it doesn't spam "unimplemented"
-    if all {
+    // such that it doesn't spam "unimplemented". Machine readable formats
+    // keep them, such that every day is represented.
+    if all && format.skips_unimplemented() {
         days_and_functions.retain(|x| x.1.is_some());
         if days_and_functions.is_empty() {
             eprintln!("No days implemented");
-            std::process::exit(0)
+            return Ok(());
         }
     }
 
-    // At this point, we should have exited if there is no work to be done.
-    // So, we can check the data directory since we know we need to load data
-    assert!(!days_and_functions.is_empty());
-    if !data_dir.is_dir() {
-        eprintln!(
-            "Data directory is not an existing directory: {:#?}",
-            data_dir
-        );
-        std::process::exit(1)
+    let run = SolveRun {
+        source,
+        data_dir,
+        days: days_and_functions,
+        format,
+        check,
+        timings,
+        parts: part.into(),
+        params,
+        jobs,
+        timeout,
+    };
+    if watch {
+        return watch::watch(&run);
     }
+    // Print the solutions and the time taken
+    let (reports, output, n_wrong) = run.run()?;
+    print!("{output}");
+    solve_outcome(&reports, n_wrong)
+}
 
-    // Load data for each implemented function. For unimplemented functions,
-    // The data is never attempted to be loaded and is just None
-    let data = load_days(data_dir, &days_and_functions);
+fn list(year: Year) {
+    for Registration {
+        day,
+        title,
+        variant,
+        ..
+    } in registrations().into_iter().filter(|r| r.year == year)
+    {
+        print!("Day {:02}: {}", day.0, title);
+        if *variant != solver::DEFAULT_VARIANT {
+            print!(" [{}]", variant)
+        }
+        println!()
+    }
+}
 
-    // For each day, if the solver+data is None, return unimplemented, else
-    // run the solver on the data and record the time spent
-    let solutions: Vec<Option<TimedBoxes>> = data
+fn params(year: Year, day: Day) -> Result<()> {
+    let solver = get_solver(year, day).ok_or(Error::Unimplemented(day))?;
+    if solver.params.is_empty() {
+        println!("Day {:02} has no parameters", day.0);
+        return Ok(());
+    }
+    let width = solver
+        .params
         .iter()
-        .map(|(_, x)| {
-            x.as_ref().map(|(f, data)| {
-                let start = std::time::Instant::now();
-                let (a, b) = f(data);
-                (start.elapsed(), a, b)
-            })
-        })
-        .collect();
-
-    // Print the time taken for the solutions
-    for ((day, _), solution) in data.iter().zip(solutions) {
-        print_solution(*day, solution)
+        .map(|p| p.name.len())
+        .max()
+        .unwrap_or(0);
+    for param in solver.params {
+        println!(
+            "{:<width$}  {} (default {})",
+            param.name, param.description, param.default
+        );
     }
+    Ok(())
 }
 
-fn download(data_dir: &Path, day_strings: Option<Vec<String>>, all: bool) {
-    // Make dir and verify it exists
-    if !data_dir.exists() {
-        if data_dir.parent().is_none() {
-            eprintln!(
-                "Data dir \"{:#?}\" does not exist, and cannot be created because it has no parent",
-                data_dir
-            );
-            std::process::exit(1)
+// `data_dir` is the data directory of the year
+fn download(
+    data_dir: &Path,
+    year: Year,
+    day_strings: Option<Vec<String>>,
+    all: bool,
+    puzzle: bool,
+) -> Result<()> {
+    let days = get_days(day_strings, all, year, Some(data_dir))?;
+    download::create_data_dir(data_dir)?;
+    // The client is only created once we need to download anything
+    let client: OnceCell<AocClient> = OnceCell::new();
+    let get_client = || -> Result<&AocClient> {
+        if let Some(client) = client.get() {
+            return Ok(client);
         }
-        std::fs::create_dir(data_dir).unwrap() // TODO: Error message
-    } else if !data_dir.is_dir() {
-        eprintln!(
-            "Data dir \"{:#?}\" exists, but is not a directory",
-            data_dir
-        );
-        std::process::exit(1)
-    }
-    let days = get_days(day_strings, all);
-    let client_cell: OnceCell<Client> = OnceCell::new();
+        let new_client = aoc_client(&Config::load()?)?;
+        Ok(client.get_or_init(|| new_client))
+    };
     for day in days.iter() {
-        let path = data_dir.join(format!("day{:0>2}.txt", day.0));
-        if path.exists() {
+        let result = if runner::input_path(data_dir, *day).exists() {
             println!("Input already exists: Day {:0>2}", day.0);
+            Ok(())
+        } else if !year.is_released(*day, SystemTime::now()) {
+            // Don't bother the server with requests for locked days
+            Err(Error::Unreleased(*day))
         } else {
             println!("Downloading day {:0>2}", day.0);
-            let client = client_cell.get_or_init(make_client);
-            if let Some(data) = download_input(client, *day) {
-                std::fs::write(path, data).unwrap()
+            download::download_input(get_client()?, data_dir, year, *day)
+        };
+        let result = result.and_then(|()| {
+            if !puzzle {
+                Ok(())
+            } else if puzzle::has_puzzle(data_dir, *day) {
+                println!("Puzzle already exists: Day {:0>2}", day.0);
+                Ok(())
             } else {
+                println!("Downloading puzzle of day {:0>2}", day.0);
+                download::download_puzzle(get_client()?, data_dir, year, *day)
+            }
+        });
+        match result {
+            Ok(()) => (),
+            // With --all, we simply download until we reach the first unreleased day
+            Err(Error::Unreleased(day)) if all => {
                 eprintln!("Day {:0>2} is not released yet!", day.0);
-                if !all {
-                    std::process::exit(1)
-                }
                 break;
             }
+            Err(e) => return Err(e),
         }
     }
-    // NOTE: If --all is passed, the day list is still untruncated here
+    Ok(())
 }
 
-fn make_client() -> Client {
-    let mut headers = reqwest::header::HeaderMap::default();
-    let session = match std::env::var("ADVENTOFCODE_SESSION") {
-        Ok(s) => s,
-        Err(e) => {
-            println!(
-                "Error: Could not load environmental variable ADVENTOFCODE_SESSION: \"{}\"",
-                e
-            );
-            std::process::exit(1);
+// One line per result, and the number of wrong answers
+fn render_example_results(results: &[ExampleResult]) -> (String, usize) {
+    let mut output = String::new();
+    let mut n_wrong = 0;
+    for result in results.iter() {
+        output += &format!(
+            "Day {:02} example {} part {}: {}",
+            result.day.0, result.index, result.part, result.answer
+        );
+        match &result.check {
+            Check::Wrong { expected } => {
+                n_wrong += 1;
+                output += &format!(" [WRONG, expected {expected}]\n")
+            }
+            check => output += &format!(" [{}]\n", check.name()),
         }
+    }
+    (output, n_wrong)
+}
+
+// `data_dir` is the data directory of the year
+fn extract_examples(
+    data_dir: &Path,
+    year: Year,
+    day_strings: Option<Vec<String>>,
+    all: bool,
+    check: bool,
+) -> Result<()> {
+    let days = get_days(day_strings, all, year, Some(data_dir))?;
+    if !data_dir.is_dir() {
+        return Err(Error::MissingDataDir(data_dir.to_owned()));
+    }
+    if check {
+        let days: Vec<_> = days
+            .iter()
+            .map(|&day| (day, get_solver(year, day)))
+            .collect();
+        let results = examples::run_examples(data_dir, &days, Parts::Both, &[])?;
+        if results.is_empty() {
+            eprintln!("No examples with expected answers found");
+        }
+        let (output, n_wrong) = render_example_results(&results);
+        print!("{output}");
+        if n_wrong > 0 {
+            return Err(Error::WrongAnswers(n_wrong));
+        }
+        return Ok(());
+    }
+    for day in days {
+        match examples::save_examples(data_dir, day) {
+            Ok((n_examples, n_answers)) => println!(
+                "Day {:02}: {} example(s) with {} expected answer(s)",
+                day.0, n_examples, n_answers
+            ),
+            // With --all, skip the days whose puzzle is not downloaded
+            Err(Error::MissingPuzzle { .. }) if all => (),
+            Err(e) => return Err(e),
+        }
+    }
+    Ok(())
+}
+
+// `data_dir` is the data directory of the year. If no answer is given, solve
+// the day and submit that.
+fn submit(data_dir: &Path, year: Year, day: Day, part: u8, answer: Option<String>) -> Result<()> {
+    let answer = match answer {
+        Some(answer) => Answer::Text(answer),
+        None => runner::solve_part(data_dir, year, day, part)?,
     };
-    let cookie =
-        reqwest::header::HeaderValue::from_str(format!("session={}", session).as_str()).unwrap();
-    headers.insert("Cookie", cookie);
-    Client::builder().default_headers(headers).build().unwrap()
+    if !answer.is_implemented() {
+        return Err(Error::UnimplementedPart { day, part });
+    }
+    let client = aoc_client(&Config::load()?)?;
+    println!(
+        "Submitting answer {} to day {:02} part {}",
+        answer, day.0, part
+    );
+    let outcome = submit::submit(&client, data_dir, year, day, part, &answer)?;
+    println!("{outcome}");
+    match outcome {
+        submit::Outcome::Correct | submit::Outcome::AlreadySolved => Ok(()),
+        _ => Err(Error::AnswerRejected),
+    }
 }
 
-// If the day is not released yet, return None
-fn download_input(client: &Client, day: Day) -> Option<String> {
-    let url = format!("https://adventofcode.com/2023/day/{}/input", day.0);
-    let resp = client.get(url.as_str()).send().unwrap();
-    if !resp.status().is_success() {
-        let text = resp.text().unwrap();
-        if text.contains("Please don't repeatedly request this endpoint before it unlocks") {
-            return None;
-        } else {
-            eprintln!("Error when processing request:\n{}", text);
-            std::process::exit(1);
+// `data_dir` is the data directory of the year
+fn visualize(
+    data_dir: &Path,
+    year: Year,
+    day: Day,
+    svg: Option<PathBuf>,
+    no_color: bool,
+) -> Result<()> {
+    match svg {
+        Some(path) => {
+            let picture = runner::visualize_day(data_dir, year, day, Picture::Svg)?;
+            std::fs::write(&path, picture).map_err(|e| Error::io(&path, e))?;
+            println!("Wrote {path:?}");
+        }
+        None => {
+            // Follow the NO_COLOR convention, and don't color output to files
+            let color = !no_color
+                && std::env::var_os("NO_COLOR").is_none()
+                && std::io::stdout().is_terminal();
+            let picture = runner::visualize_day(data_dir, year, day, Picture::Text { color })?;
+            print!("{picture}");
         }
     }
-    Some(resp.text().unwrap())
+    Ok(())
+}
+
+// A client for the configuration, which tells the user about its warnings and
+// retried requests
+fn aoc_client(config: &Config) -> Result<AocClient> {
+    if let Some(warning) = config.client.warning() {
+        eprintln!("Warning: {warning}");
+    }
+    Ok(AocClient::from_config(config)?.on_retry(|url, delay| {
+        eprintln!(
+            "Request to {url} failed, retrying in {:.1}s",
+            delay.as_secs_f64()
+        )
+    }))
+}
+
+fn whoami(year: Year) -> Result<()> {
+    let config = Config::load()?;
+    let session = config.session()?;
+    match aoc_client(&config)?.user(year)? {
+        Some(user) => {
+            println!(
+                "Logged in as {user}, with the session token from the {}",
+                session.source
+            );
+            Ok(())
+        }
+        None => Err(Error::ExpiredSession),
+    }
 }
 
 #[derive(Subcommand)]
 enum Commands {
     Solve {
-        data_dir: PathBuf,
+        /// The data directory, or - to read the input of a single day from standard input.
+        /// With --input or --input-pattern, this is the first day instead
+        #[arg(value_name = "DATA_DIR")]
+        first_arg: Option<PathBuf>,
         day_strings: Option<Vec<String>>,
         #[arg(long)]
         all: bool,
+        #[command(flatten)]
+        options: SolveOptions,
     },
+    /// List the implemented days of the year
+    List,
+    /// List the parameters of a day, which `solve --param` can set
+    Params { day: Day },
     Download {
         data_dir: PathBuf,
         day_strings: Option<Vec<String>>,
         #[arg(long)]
         all: bool,
-    }, // TODO: Add benchmark (reading, parsing, solving)
+        /// Also download the puzzle descriptions, and convert them to Markdown
+        #[arg(long)]
+        puzzle: bool,
+    },
+    /// Print the downloaded puzzle description of a day
+    Show { data_dir: PathBuf, day: Day },
+    /// Submit an answer to Advent of Code. If no answer is given, solve the day and submit that
+    Submit {
+        data_dir: PathBuf,
+        day: Day,
+        #[arg(value_parser = clap::value_parser!(u8).range(1..=2))]
+        part: u8,
+        answer: Option<String>,
+    },
+    /// Extract the examples and their answers from the downloaded puzzle descriptions
+    Examples {
+        data_dir: PathBuf,
+        day_strings: Option<Vec<String>>,
+        #[arg(long)]
+        all: bool,
+        /// Instead of extracting, solve the extracted examples and compare to their answers
+        #[arg(long)]
+        check: bool,
+    },
+    /// Draw the input of a day, e.g. the pipe loop of 2023 day 10
+    Visualize {
+        data_dir: PathBuf,
+        day: Day,
+        /// Write an SVG image to this file instead of drawing in the terminal
+        #[arg(long)]
+        svg: Option<PathBuf>,
+        /// Don't color the drawing, even in a terminal
+        #[arg(long)]
+        no_color: bool,
+    },
+    /// Check that the session token is valid, and show the user it belongs to
+    Whoami,
+    /// Time reading, parsing and solving each day over many runs
+    Bench {
+        data_dir: PathBuf,
+        day_strings: Option<Vec<String>>,
+        #[arg(long)]
+        all: bool,
+        /// Minimum number of runs of each day
+        #[arg(long, default_value_t = 10)]
+        iterations: usize,
+        /// Minimum time in seconds to spend running each day
+        #[arg(long, default_value = "1", value_parser = parse_seconds)]
+        min_time: Duration,
+        /// Run only this part (1 or 2) of each day
+        #[arg(long, value_parser = clap::value_parser!(u8).range(1..=2))]
+        part: Option<u8>,
+    },
 }
 
 #[derive(Parser)]
 #[command(author, version, about, long_about = None)]
 struct Cli {
+    /// The year of the puzzles. Its data is stored in a subdirectory of the data directory
+    #[arg(long, global = true, default_value_t = DEFAULT_YEAR)]
+    year: Year,
     #[command(subcommand)]
     command: Commands,
 }
 
-fn main() {
+fn main() -> ExitCode {
     let cli = Cli::parse();
-    match cli.command {
+    let year = cli.year;
+    let result = match cli.command {
         Commands::Solve {
-            data_dir,
+            first_arg,
             day_strings,
             all,
-        } => solve(&data_dir, day_strings, all),
+            options,
+        } => solve(first_arg, year, day_strings, all, options),
+        Commands::List => {
+            list(year);
+            Ok(())
+        }
+        Commands::Params { day } => year.check_day(day).and_then(|day| params(year, day)),
         Commands::Download {
             data_dir,
             day_strings,
             all,
-        } => download(&data_dir, day_strings, all),
+            puzzle,
+        } => download(
+            &year_data_dir(&data_dir, year),
+            year,
+            day_strings,
+            all,
+            puzzle,
+        ),
+        Commands::Show { data_dir, day } => year
+            .check_day(day)
+            .and_then(|day| puzzle::show(&year_data_dir(&data_dir, year), day)),
+        Commands::Submit {
+            data_dir,
+            day,
+            part,
+            answer,
+        } => year
+            .check_day(day)
+            .and_then(|day| submit(&year_data_dir(&data_dir, year), year, day, part, answer)),
+        Commands::Examples {
+            data_dir,
+            day_strings,
+            all,
+            check,
+        } => extract_examples(
+            &year_data_dir(&data_dir, year),
+            year,
+            day_strings,
+            all,
+            check,
+        ),
+        Commands::Visualize {
+            data_dir,
+            day,
+            svg,
+            no_color,
+        } => year
+            .check_day(day)
+            .and_then(|day| visualize(&year_data_dir(&data_dir, year), year, day, svg, no_color)),
+        Commands::Whoami => whoami(year),
+        Commands::Bench {
+            data_dir,
+            day_strings,
+            all,
+            iterations,
+            min_time,
+            part,
+        } => bench::bench(
+            &year_data_dir(&data_dir, year),
+            year,
+            day_strings,
+            all,
+            iterations,
+            min_time,
+            part.into(),
+        ),
+    };
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("Error: {e}");
+            ExitCode::from(e.exit_code())
+        }
     }
 }
@@ -0,0 +1,604 @@
+use std::{fmt::Write, time::Duration};
+
+use clap::ValueEnum;
+
+use crate::{
+    answer::Answer,
+    answers::Check,
+    input::ParseResult,
+    solver::{Failure, Solution, Timings},
+    Day,
+};
+
+/// How `solve` prints the solutions
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, ValueEnum)]
+pub enum Format {
+    /// Human readable
+    #[default]
+    Text,
+    /// A JSON array with one object per day
+    Json,
+    /// Comma separated values with a header
+    Csv,
+    /// Tab separated values with a header
+    Tsv,
+}
+
+impl Format {
+    /// Whether days are omitted from the output when they are unimplemented and
+    /// not explicitly requested. Machine readable formats list all days.
+    pub fn skips_unimplemented(self) -> bool {
+        self == Format::Text
+    }
+}
+
+/// The outcome of solving one day
+pub struct DayReport {
+    pub day: Day,
+    /// None if the day is unimplemented, or the failure if it could not be solved
+    pub solution: Option<Result<Solution, Failure>>,
+    /// The result of checking each part against the stored answers, if checked
+    pub checks: Option<[Check; 2]>,
+}
+
+// The value of a field of the machine readable formats
+enum Value {
+    // A number or boolean, which JSON writes as is
+    Literal(String),
+    Text(String),
+}
+
+impl Value {
+    fn into_string(self) -> String {
+        match self {
+            Value::Literal(s) | Value::Text(s) => s,
+        }
+    }
+}
+
+fn literal(x: impl ToString) -> Value {
+    Value::Literal(x.to_string())
+}
+
+// The errors of the parts that found the input malformed, if any
+fn part_errors(solution: &Solution) -> Option<String> {
+    let errors: Vec<_> = [1, 2]
+        .into_iter()
+        .filter_map(|part| match solution.result(part)? {
+            Err(e) => Some(format!("part {part}: {e}")),
+            Ok(_) => None,
+        })
+        .collect();
+    (!errors.is_empty()).then(|| errors.join("; "))
+}
+
+// Integer answers are numbers. Big integers are text, as many JSON parsers
+// read numbers as 64-bit integers or doubles, and would silently round them.
+fn answer_value(answer: &Answer) -> Option<Value> {
+    match answer {
+        Answer::Signed(n) => Some(literal(n)),
+        Answer::Unsigned(n) => Some(literal(n)),
+        Answer::Big(n) => Some(Value::Text(n.to_string())),
+        Answer::Text(s) => Some(Value::Text(s.clone())),
+        Answer::Unimplemented => None,
+    }
+}
+
+// One row of the machine readable formats. Fields are None if unknown.
+struct Record<'a> {
+    day: u8,
+    implemented: bool,
+    results: Option<[Option<&'a ParseResult<Answer>>; 2]>,
+    time_ns: Option<u128>,
+    timings: Option<Timings>,
+    error: Option<String>,
+    checks: Option<&'a [Check; 2]>,
+}
+
+impl<'a> Record<'a> {
+    fn new(report: &'a DayReport) -> Self {
+        let solution = report.solution.as_ref().and_then(|s| s.as_ref().ok());
+        Record {
+            day: report.day.0,
+            implemented: report.solution.is_some(),
+            results: solution.map(|s| [s.part1.as_ref(), s.part2.as_ref()]),
+            time_ns: solution.map(|s| s.timings.total().as_nanos()),
+            timings: solution.map(|s| s.timings),
+            error: match &report.solution {
+                Some(Err(e)) => Some(e.to_string()),
+                Some(Ok(s)) => part_errors(s),
+                None => None,
+            },
+            checks: report.checks.as_ref(),
+        }
+    }
+
+    // Whether part i + 1 was run
+    fn solved(&self, i: usize) -> bool {
+        self.results.is_some_and(|r| r[i].is_some())
+    }
+
+    // Whether part i + 1 was not asked for, while the day was solved
+    fn excluded(&self, i: usize) -> bool {
+        self.results.is_some_and(|r| r[i].is_none())
+    }
+
+    // The names and values of all fields. If `checked`, include the status and
+    // expected answer of each part. If `timings`, include the time of each phase.
+    fn fields(&self, checked: bool, timings: bool) -> Vec<(&'static str, Option<Value>)> {
+        // Parts that were not solved, failed or are not implemented have no answer
+        let answer = |i: usize| answer_value(self.results?[i]?.as_ref().ok()?);
+        let mut v = vec![
+            ("day", Some(literal(self.day))),
+            ("implemented", Some(literal(self.implemented))),
+            ("part1", answer(0)),
+            ("part2", answer(1)),
+            ("time_ns", self.time_ns.map(literal)),
+        ];
+        if timings {
+            let t = self.timings.as_ref();
+            for (name, time) in [
+                ("parse_ns", t.map(|t| t.parse)),
+                ("part1_ns", t.filter(|_| self.solved(0)).map(|t| t.part1)),
+                ("part2_ns", t.filter(|_| self.solved(1)).map(|t| t.part2)),
+            ] {
+                v.push((name, time.map(|d| literal(d.as_nanos()))));
+            }
+        }
+        v.push(("error", self.error.clone().map(Value::Text)));
+        if checked {
+            for (i, (status, expected)) in [
+                ("part1_status", "part1_expected"),
+                ("part2_status", "part2_expected"),
+            ]
+            .into_iter()
+            .enumerate()
+            {
+                let check = self.checks.filter(|_| self.solved(i)).map(|c| &c[i]);
+                v.push((status, check.map(|c| Value::Text(c.name().to_owned()))));
+                v.push((
+                    expected,
+                    check.and_then(|c| c.expected().map(|e| Value::Text(e.to_owned()))),
+                ));
+            }
+        }
+        v
+    }
+}
+
+/// Render the reports. If `checked`, show the result of checking each answer.
+/// If `timings`, show the time of each phase, and the total of all days.
+pub fn render(format: Format, reports: &[DayReport], checked: bool, timings: bool) -> String {
+    let mut buf = String::new();
+    match format {
+        Format::Text => {
+            for report in reports.iter() {
+                render_text(&mut buf, report, timings)
+            }
+            if timings {
+                render_totals(&mut buf, reports)
+            }
+        }
+        Format::Json => render_json(&mut buf, reports, checked, timings),
+        Format::Csv => render_separated(&mut buf, reports, checked, timings, ',', csv_field),
+        Format::Tsv => render_separated(&mut buf, reports, checked, timings, '\t', tsv_field),
+    }
+    buf
+}
+
+fn render_text(buf: &mut String, report: &DayReport, show_timings: bool) {
+    write!(buf, "Day {:02}", report.day.0).unwrap();
+    if let Some(Err(e)) = &report.solution {
+        writeln!(buf, ": {e}\n").unwrap()
+    } else if let Some(Ok(Solution {
+        timings,
+        part1,
+        part2,
+    })) = &report.solution
+    {
+        writeln!(buf, " [{:.2?}]:", timings.total()).unwrap();
+        if show_timings {
+            writeln!(buf, "  Parse [{:.2?}]", timings.parse).unwrap();
+        }
+        for (i, result) in [part1, part2].into_iter().enumerate() {
+            // Only the parts that were asked for are solved
+            let Some(result) = result else {
+                continue;
+            };
+            write!(buf, "  Part {}", i + 1).unwrap();
+            if show_timings {
+                let time = if i == 0 { timings.part1 } else { timings.part2 };
+                write!(buf, " [{:.2?}]", time).unwrap();
+            }
+            let answer = match result {
+                Ok(answer) => answer,
+                Err(e) => {
+                    writeln!(buf, ": {e}").unwrap();
+                    continue;
+                }
+            };
+            write!(buf, ": {}", answer).unwrap();
+            match report.checks.as_ref().map(|c| &c[i]) {
+                None => (),
+                Some(Check::Correct) => buf.push_str(" [correct]"),
+                Some(Check::Unknown) => buf.push_str(" [unknown]"),
+                Some(Check::Pending) => (),
+                Some(Check::Wrong { expected }) => {
+                    write!(buf, " [WRONG, expected {}]", expected).unwrap()
+                }
+            }
+            buf.push('\n');
+        }
+        buf.push('\n');
+    } else {
+        writeln!(buf, ":\n  Unimplemented!\n").unwrap()
+    }
+}
+
+// The sum of the timings of all solved days
+fn render_totals(buf: &mut String, reports: &[DayReport]) {
+    let timings: Vec<_> = reports
+        .iter()
+        .filter_map(|r| r.solution.as_ref()?.as_ref().ok())
+        .map(|s| s.timings)
+        .collect();
+    let total = |phase: fn(&Timings) -> Duration| timings.iter().map(phase).sum::<Duration>();
+    writeln!(
+        buf,
+        "Total of {} day(s) [{:.2?}]: parse {:.2?}, part 1 {:.2?}, part 2 {:.2?}",
+        timings.len(),
+        total(Timings::total),
+        total(|t| t.parse),
+        total(|t| t.part1),
+        total(|t| t.part2)
+    )
+    .unwrap()
+}
+
+fn render_json(buf: &mut String, reports: &[DayReport], checked: bool, timings: bool) {
+    buf.push_str("[\n");
+    for (i, report) in reports.iter().enumerate() {
+        let record = Record::new(report);
+        // The fields of excluded parts are left out, to tell them apart from
+        // pending parts, whose answer is null
+        let fields = record
+            .fields(checked, timings)
+            .into_iter()
+            .filter(|(name, _)| {
+                !(0..2).any(|i| record.excluded(i) && name.starts_with(&format!("part{}", i + 1)))
+            });
+        buf.push_str("  {");
+        for (j, (name, value)) in fields.enumerate() {
+            if j > 0 {
+                buf.push_str(", ");
+            }
+            let value = match value {
+                None => "null".to_owned(),
+                Some(Value::Literal(v)) => v,
+                Some(Value::Text(v)) => json_string(&v),
+            };
+            write!(buf, "\"{}\": {}", name, value).unwrap();
+        }
+        buf.push('}');
+        buf.push_str(if i + 1 < reports.len() { ",\n" } else { "\n" });
+    }
+    buf.push_str("]\n");
+}
+
+fn json_string(s: &str) -> String {
+    let mut result = String::with_capacity(s.len() + 2);
+    result.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => result.push_str("\\\""),
+            '\\' => result.push_str("\\\\"),
+            '\n' => result.push_str("\\n"),
+            '\r' => result.push_str("\\r"),
+            '\t' => result.push_str("\\t"),
+            c if (c as u32) < 0x20 => write!(result, "\\u{:04x}", c as u32).unwrap(),
+            c => result.push(c),
+        }
+    }
+    result.push('"');
+    result
+}
+
+fn render_separated(
+    buf: &mut String,
+    reports: &[DayReport],
+    checked: bool,
+    timings: bool,
+    separator: char,
+    escape: fn(&str) -> String,
+) {
+    let write_row = |buf: &mut String, fields: &mut dyn Iterator<Item = String>| {
+        for (i, field) in fields.enumerate() {
+            if i > 0 {
+                buf.push(separator);
+            }
+            buf.push_str(&escape(&field));
+        }
+        buf.push('\n');
+    };
+    // The header is the same for all records, so we can use the one of an empty day
+    let empty = Record {
+        day: 0,
+        implemented: false,
+        results: None,
+        time_ns: None,
+        timings: None,
+        error: None,
+        checks: None,
+    };
+    write_row(
+        buf,
+        &mut empty
+            .fields(checked, timings)
+            .into_iter()
+            .map(|(name, _)| name.to_owned()),
+    );
+    for report in reports.iter() {
+        let record = Record::new(report);
+        write_row(
+            buf,
+            &mut record
+                .fields(checked, timings)
+                .into_iter()
+                .map(|(_, v)| v.map(Value::into_string).unwrap_or_default()),
+        );
+    }
+}
+
+// Quote fields as described in RFC 4180
+fn csv_field(s: &str) -> String {
+    if s.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", s.replace('"', "\"\""))
+    } else {
+        s.to_owned()
+    }
+}
+
+// TSV has no quoting, so escape the characters that would break the format
+fn tsv_field(s: &str) -> String {
+    s.replace('\\', "\\\\")
+        .replace('\t', "\\t")
+        .replace('\n', "\\n")
+        .replace('\r', "\\r")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{render, DayReport, Format};
+    use crate::{
+        answer::Answer,
+        answers::Check,
+        input::ParseError,
+        solver::{Solution, Timings},
+        Day,
+    };
+    use std::time::Duration;
+
+    fn solutions() -> Vec<DayReport> {
+        vec![
+            DayReport {
+                day: Day(1),
+                solution: Some(Ok(Solution {
+                    timings: Timings {
+                        parse: Duration::from_nanos(100),
+                        part1: Duration::from_nanos(20),
+                        part2: Duration::from_nanos(3),
+                    },
+                    part1: Some(Ok(142.into())),
+                    part2: Some(Ok("a,\"b\"\tc".into())),
+                })),
+                checks: Some([
+                    Check::Correct,
+                    Check::Wrong {
+                        expected: "x".to_owned(),
+                    },
+                ]),
+            },
+            DayReport {
+                day: Day(2),
+                solution: None,
+                checks: None,
+            },
+            DayReport {
+                day: Day(3),
+                solution: Some(Err(ParseError::new(12, "invalid card 'X'").into())),
+                checks: None,
+            },
+        ]
+    }
+
+    #[test]
+    fn test_json() {
+        assert_eq!(
+            render(Format::Json, &solutions(), false, false),
+            "[
+  {\"day\": 1, \"implemented\": true, \"part1\": 142, \"part2\": \"a,\\\"b\\\"\\tc\", \"time_ns\": 123, \"error\": null},
+  {\"day\": 2, \"implemented\": false, \"part1\": null, \"part2\": null, \"time_ns\": null, \"error\": null},
+  {\"day\": 3, \"implemented\": true, \"part1\": null, \"part2\": null, \"time_ns\": null, \"error\": \"input error at line 12: invalid card 'X'\"}
+]
+"
+        );
+    }
+
+    #[test]
+    fn test_csv() {
+        assert_eq!(
+            render(Format::Csv, &solutions(), false, false),
+            "day,implemented,part1,part2,time_ns,error
+1,true,142,\"a,\"\"b\"\"\tc\",123,
+2,false,,,,
+3,true,,,,input error at line 12: invalid card 'X'
+"
+        );
+    }
+
+    #[test]
+    fn test_tsv() {
+        assert_eq!(
+            render(Format::Tsv, &solutions(), false, false),
+            "day\timplemented\tpart1\tpart2\ttime_ns\terror
+1\ttrue\t142\ta,\"b\"\\tc\t123\t
+2\tfalse\t\t\t\t
+3\ttrue\t\t\t\tinput error at line 12: invalid card 'X'
+"
+        );
+    }
+
+    #[test]
+    fn test_checked() {
+        assert_eq!(
+            render(Format::Text, &solutions(), true, false),
+            "Day 01 [123.00ns]:
+  Part 1: 142 [correct]
+  Part 2: a,\"b\"\tc [WRONG, expected x]
+
+Day 02:
+  Unimplemented!
+
+Day 03: input error at line 12: invalid card 'X'
+
+"
+        );
+        assert_eq!(
+            render(Format::Csv, &solutions(), true, false),
+            "day,implemented,part1,part2,time_ns,error,part1_status,part1_expected,part2_status,part2_expected
+1,true,142,\"a,\"\"b\"\"\tc\",123,,correct,,wrong,x
+2,false,,,,,,,,
+3,true,,,,input error at line 12: invalid card 'X',,,,
+"
+        );
+    }
+
+    #[test]
+    fn test_timings() {
+        assert_eq!(
+            render(Format::Text, &solutions()[..1], false, true),
+            "Day 01 [123.00ns]:
+  Parse [100.00ns]
+  Part 1 [20.00ns]: 142 [correct]
+  Part 2 [3.00ns]: a,\"b\"\tc [WRONG, expected x]
+
+Total of 1 day(s) [123.00ns]: parse 100.00ns, part 1 20.00ns, part 2 3.00ns
+"
+        );
+        assert_eq!(
+            render(Format::Csv, &solutions(), false, true),
+            "day,implemented,part1,part2,time_ns,parse_ns,part1_ns,part2_ns,error
+1,true,142,\"a,\"\"b\"\"\tc\",123,100,20,3,
+2,false,,,,,,,
+3,true,,,,,,,input error at line 12: invalid card 'X'
+"
+        );
+    }
+
+    #[test]
+    fn test_partial() {
+        let reports = [
+            DayReport {
+                day: Day(4),
+                solution: Some(Ok(Solution {
+                    timings: Timings::default(),
+                    part1: Some(Ok(13.into())),
+                    part2: Some(Ok(Answer::Unimplemented)),
+                })),
+                checks: Some([Check::Correct, Check::Pending]),
+            },
+            // Solved with --part 2
+            DayReport {
+                day: Day(5),
+                solution: Some(Ok(Solution {
+                    timings: Timings::default(),
+                    part1: None,
+                    part2: Some(Ok(46.into())),
+                })),
+                checks: Some([Check::Unknown, Check::Correct]),
+            },
+        ];
+        assert_eq!(
+            render(Format::Text, &reports, true, false),
+            "Day 04 [0.00ns]:
+  Part 1: 13 [correct]
+  Part 2: pending
+
+Day 05 [0.00ns]:
+  Part 2: 46 [correct]
+
+"
+        );
+        assert_eq!(
+            render(Format::Csv, &reports, true, true),
+            "day,implemented,part1,part2,time_ns,parse_ns,part1_ns,part2_ns,error,part1_status,part1_expected,part2_status,part2_expected
+4,true,13,,0,0,0,0,,correct,,pending,
+5,true,,46,0,0,,0,,,,correct,
+"
+        );
+        assert_eq!(
+            render(Format::Json, &reports, false, false),
+            "[
+  {\"day\": 4, \"implemented\": true, \"part1\": 13, \"part2\": null, \"time_ns\": 0, \"error\": null},
+  {\"day\": 5, \"implemented\": true, \"part2\": 46, \"time_ns\": 0, \"error\": null}
+]
+"
+        );
+        assert_eq!(
+            render(Format::Json, &reports[1..], true, true),
+            "[
+  {\"day\": 5, \"implemented\": true, \"part2\": 46, \"time_ns\": 0, \"parse_ns\": 0, \"part2_ns\": 0, \"error\": null, \"part2_status\": \"correct\", \"part2_expected\": null}
+]
+"
+        );
+    }
+
+    #[test]
+    fn test_json_answers() {
+        let reports = [DayReport {
+            day: Day(9),
+            solution: Some(Ok(Solution {
+                timings: Timings::default(),
+                part1: Some(Ok((-3).into())),
+                part2: Some(Ok(u128::MAX.into())),
+            })),
+            checks: None,
+        }];
+        // Big integers stay strings, such that parsers don't round them
+        assert_eq!(
+            render(Format::Json, &reports, false, false),
+            format!(
+                "[\n  {{\"day\": 9, \"implemented\": true, \"part1\": -3, \"part2\": \"{}\", \"time_ns\": 0, \"error\": null}}\n]\n",
+                u128::MAX
+            )
+        );
+    }
+
+    #[test]
+    fn test_part_error() {
+        // Part 1 finds the input malformed, part 2 is still solved
+        let reports = [DayReport {
+            day: Day(8),
+            solution: Some(Ok(Solution {
+                timings: Timings::default(),
+                part1: Some(Err(ParseError::new(1, "there is no start node 'AAA'"))),
+                part2: Some(Ok(6.into())),
+            })),
+            checks: Some([Check::Unknown, Check::Correct]),
+        }];
+        assert_eq!(
+            render(Format::Text, &reports, true, false),
+            "Day 08 [0.00ns]:
+  Part 1: input error at line 1: there is no start node 'AAA'
+  Part 2: 6 [correct]
+
+"
+        );
+        assert_eq!(
+            render(Format::Csv, &reports, false, false),
+            "day,implemented,part1,part2,time_ns,error
+8,true,,6,0,part 1: input error at line 1: there is no start node 'AAA'
+"
+        );
+    }
+}
@@ -0,0 +1,61 @@
+use std::{
+    num::NonZeroUsize,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Mutex,
+    },
+};
+
+/// The number of threads to use if the user asks for 0 jobs
+pub fn default_jobs() -> usize {
+    std::thread::available_parallelism().map_or(1, NonZeroUsize::get)
+}
+
+/// Apply `f` to each item using `jobs` threads, returning the results in the
+/// same order as the items. Items are handed out one at a time, so a slow item
+/// does not hold up the others.
+pub fn map<T, R, F>(items: &[T], jobs: usize, f: F) -> Vec<R>
+where
+    T: Sync,
+    R: Send,
+    F: Fn(&T) -> R + Sync,
+{
+    let jobs = jobs.clamp(1, items.len().max(1));
+    // Don't spawn threads when running serially, to keep timings clean
+    if jobs == 1 {
+        return items.iter().map(f).collect();
+    }
+    let next = AtomicUsize::new(0);
+    let results: Mutex<Vec<Option<R>>> = Mutex::new((0..items.len()).map(|_| None).collect());
+    std::thread::scope(|scope| {
+        for _ in 0..jobs {
+            scope.spawn(|| loop {
+                let i = next.fetch_add(1, Ordering::Relaxed);
+                let Some(item) = items.get(i) else {
+                    break;
+                };
+                let result = f(item);
+                results.lock().unwrap()[i] = Some(result);
+            });
+        }
+    });
+    results
+        .into_inner()
+        .unwrap()
+        .into_iter()
+        .map(|r| r.expect("all items are processed when the scope ends"))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn test_map() {
+        let items: Vec<u64> = (0..100).collect();
+        for jobs in [1, 3, 200] {
+            let result = super::map(&items, jobs, |&i| i * i);
+            assert_eq!(result, items.iter().map(|i| i * i).collect::<Vec<_>>());
+        }
+        assert!(super::map(&Vec::<u8>::new(), 4, |&i| i).is_empty());
+    }
+}
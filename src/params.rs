@@ -0,0 +1,121 @@
+// Values that a puzzle fixes but could just as well be different, like the
+// expansion factor of day 11. Days declare them in `Solver::PARAMS`, and they
+// can be changed with `--param name=value`, e.g. to check the values used in
+// the examples of a puzzle.
+
+use std::{collections::BTreeMap, str::FromStr};
+
+/// A parameter of a day, with its default value
+#[derive(Debug, Clone, Copy)]
+pub struct Param {
+    pub name: &'static str,
+    pub default: &'static str,
+    pub description: &'static str,
+    // Whether a value can be parsed as the type of the parameter
+    is_valid: fn(&str) -> bool,
+}
+
+fn is_valid<T: FromStr>(s: &str) -> bool {
+    s.parse::<T>().is_ok()
+}
+
+impl Param {
+    /// A parameter whose values are parsed as `T`
+    pub const fn new<T: FromStr>(
+        name: &'static str,
+        default: &'static str,
+        description: &'static str,
+    ) -> Self {
+        Param {
+            name,
+            default,
+            description,
+            is_valid: is_valid::<T>,
+        }
+    }
+}
+
+/// The values of all parameters of a day
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Params(BTreeMap<&'static str, String>);
+
+impl Params {
+    pub fn defaults(params: &[Param]) -> Self {
+        Params(
+            params
+                .iter()
+                .map(|p| (p.name, p.default.to_owned()))
+                .collect(),
+        )
+    }
+
+    /// The declared `params`, with the values in `assignments` where given.
+    /// Assignments of parameters that are not declared are ignored, such that
+    /// they can be given for several days at once. Returns the reason if a
+    /// value is invalid.
+    pub fn new(params: &[Param], assignments: &[(String, String)]) -> Result<Self, String> {
+        let mut values = Params::defaults(params);
+        for (name, value) in assignments {
+            let Some(param) = params.iter().find(|p| p.name == name) else {
+                continue;
+            };
+            if !(param.is_valid)(value) {
+                return Err(format!("invalid value \"{value}\" for parameter {name}"));
+            }
+            values.0.insert(param.name, value.clone());
+        }
+        Ok(values)
+    }
+
+    /// The value of a declared parameter. Panics if the parameter is not
+    /// declared, as values are checked when the parameters are made.
+    pub fn get<T: FromStr>(&self, name: &str) -> T {
+        self.0
+            .get(name)
+            .and_then(|value| value.parse().ok())
+            .unwrap_or_else(|| panic!("Parameter {name} is not declared with this type"))
+    }
+}
+
+/// Parse a `name=value` assignment of the command line
+pub fn parse_assignment(s: &str) -> Result<(String, String), String> {
+    match s.split_once('=') {
+        Some((name, value)) if !name.trim().is_empty() => {
+            Ok((name.trim().to_owned(), value.trim().to_owned()))
+        }
+        _ => Err(format!("\"{s}\" is not of the form name=value")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{parse_assignment, Param, Params};
+
+    const PARAMS: &[Param] = &[
+        Param::new::<usize>("expansion", "2", "How much empty rows expand"),
+        Param::new::<char>("end", "Z", "The last letter of end nodes"),
+    ];
+
+    #[test]
+    fn test_params() {
+        let defaults = Params::defaults(PARAMS);
+        assert_eq!(defaults.get::<usize>("expansion"), 2);
+        assert_eq!(defaults.get::<char>("end"), 'Z');
+
+        let assignments = [
+            parse_assignment("expansion=10").unwrap(),
+            parse_assignment("other = x").unwrap(),
+        ];
+        let params = Params::new(PARAMS, &assignments).unwrap();
+        assert_eq!(params.get::<usize>("expansion"), 10);
+        assert_eq!(params.get::<char>("end"), 'Z');
+
+        let bad = [parse_assignment("end=ZZ").unwrap()];
+        assert_eq!(
+            Params::new(PARAMS, &bad),
+            Err("invalid value \"ZZ\" for parameter end".to_owned())
+        );
+        assert!(parse_assignment("=1").is_err());
+        assert!(parse_assignment("expansion").is_err());
+    }
+}
@@ -0,0 +1,212 @@
+// Puzzle descriptions are stored next to the input in the data directory:
+// dayNN.html contains the <article class="day-desc"> elements of the puzzle page,
+// and dayNN.md is the same text converted to Markdown, for reading.
+
+use std::path::{Path, PathBuf};
+
+use crate::{
+    client::BASE_URL,
+    error::{Error, Result},
+    Day,
+};
+
+pub fn html_path(data_dir: &Path, day: Day) -> PathBuf {
+    data_dir.join(format!("day{:02}.html", day.0))
+}
+
+pub fn markdown_path(data_dir: &Path, day: Day) -> PathBuf {
+    data_dir.join(format!("day{:02}.md", day.0))
+}
+
+/// Get the `<article class="day-desc">` elements of a puzzle page. There is one
+/// for part 1, and one more for part 2 once part 1 is solved.
+pub fn extract_articles(html: &str) -> Vec<&str> {
+    let mut result = Vec::new();
+    let mut rest = html;
+    while let Some(start) = rest.find("<article class=\"day-desc\">") {
+        let Some(len) = rest[start..].find("</article>") else {
+            break;
+        };
+        let end = start + len + "</article>".len();
+        result.push(&rest[start..end]);
+        rest = &rest[end..];
+    }
+    result
+}
+
+pub fn decode_entities(s: &str) -> String {
+    s.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&apos;", "'")
+        .replace("&amp;", "&")
+}
+
+// Get the value of an attribute from the inside of a tag, e.g. `a href="/2023"`
+fn attribute<'a>(tag: &'a str, name: &str) -> Option<&'a str> {
+    let pattern = format!("{name}=\"");
+    let start = tag.find(&pattern)? + pattern.len();
+    let len = tag[start..].find('"')?;
+    Some(&tag[start..start + len])
+}
+
+/// Convert the HTML of the puzzle articles to Markdown. Only the handful of
+/// elements used in puzzle descriptions are converted; other tags are dropped.
+pub fn html_to_markdown(html: &str) -> String {
+    let mut md = String::new();
+    let mut in_pre = false;
+    let mut in_code = false;
+    let mut link: Option<String> = None;
+    let mut rest = html;
+    while !rest.is_empty() {
+        if let Some(after) = rest.strip_prefix('<') {
+            let Some(end) = after.find('>') else {
+                break;
+            };
+            let tag = &after[..end];
+            rest = &after[end + 1..];
+            let name = tag.split_whitespace().next().unwrap_or("");
+            match name {
+                "h2" => md.push_str("## "),
+                "/h2" | "/p" => md.push_str("\n\n"),
+                "pre" => {
+                    in_pre = true;
+                    md.push_str("```\n")
+                }
+                "/pre" => {
+                    in_pre = false;
+                    if !md.ends_with('\n') {
+                        md.push('\n')
+                    }
+                    md.push_str("```\n\n")
+                }
+                "code" if !in_pre => {
+                    in_code = true;
+                    md.push('`')
+                }
+                "/code" if !in_pre => {
+                    in_code = false;
+                    md.push('`')
+                }
+                "em" | "/em" if !in_pre && !in_code => md.push('*'),
+                "li" => md.push_str("- "),
+                "/li" => md.push('\n'),
+                "/ul" => md.push('\n'),
+                "a" => {
+                    link = attribute(tag, "href").map(|href| {
+                        if href.starts_with('/') {
+                            format!("{BASE_URL}{href}")
+                        } else {
+                            href.to_owned()
+                        }
+                    });
+                    md.push('[')
+                }
+                "/a" => match link.take() {
+                    Some(href) => md.push_str(&format!("]({href})")),
+                    None => md.push(']'),
+                },
+                _ => (),
+            }
+        } else {
+            let end = rest.find('<').unwrap_or(rest.len());
+            let text = decode_entities(&rest[..end]);
+            rest = &rest[end..];
+            if in_pre {
+                md.push_str(&text)
+            } else {
+                // Collapse whitespace, but not at the start of a line
+                let collapsed = text.split_whitespace().collect::<Vec<_>>().join(" ");
+                if text.starts_with(char::is_whitespace)
+                    && !md.ends_with(char::is_whitespace)
+                    && !collapsed.is_empty()
+                {
+                    md.push(' ')
+                }
+                md.push_str(&collapsed);
+                if text.ends_with(char::is_whitespace) && !collapsed.is_empty() {
+                    md.push(' ')
+                }
+            }
+        }
+    }
+    md.trim_end().to_owned() + "\n"
+}
+
+/// Whether the puzzle description of the day is downloaded with both parts
+pub fn has_puzzle(data_dir: &Path, day: Day) -> bool {
+    std::fs::read_to_string(html_path(data_dir, day))
+        .is_ok_and(|existing| extract_articles(&existing).len() == 2)
+}
+
+/// Print the downloaded puzzle description of the day
+pub fn show(data_dir: &Path, day: Day) -> Result<()> {
+    let path = markdown_path(data_dir, day);
+    match std::fs::read_to_string(&path) {
+        Ok(s) => {
+            print!("{s}");
+            Ok(())
+        }
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+            Err(Error::MissingPuzzle { day, path })
+        }
+        Err(e) => Err(Error::io(path, e)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{extract_articles, html_to_markdown};
+
+    static PAGE: &str = r#"<main>
+<article class="day-desc"><h2>--- Day 1: Trebuchet?! ---</h2><p>Something is wrong with global snow production, and you've been selected to take a look. The Elves have even given you a <a href="/2023/day/1/map">map</a>; on it, they've used stars to mark the top fifty locations.</p>
+<p>For example:</p>
+<pre><code>1abc2
+pqr3stu8vwx
+</code></pre>
+<p>In this example, the calibration values of these four lines are <code>12</code> and <code>38</code>. Adding these together produces <code><em>142</em></code>.</p>
+<ul>
+<li>One &amp; <em>two</em></li>
+</ul>
+</article>
+<p>Your puzzle answer was <code>57346</code>.</p>
+<article class="day-desc"><h2 id="part2">--- Part Two ---</h2><p>Your calculation isn't quite right.</p>
+</article>
+</main>"#;
+
+    #[test]
+    fn test_extract() {
+        let articles = extract_articles(PAGE);
+        assert_eq!(articles.len(), 2);
+        assert!(articles[1].starts_with("<article class=\"day-desc\"><h2 id=\"part2\">"));
+        assert!(articles[1].ends_with("</article>"));
+    }
+
+    #[test]
+    fn test_markdown() {
+        let html = extract_articles(PAGE).join("\n");
+        assert_eq!(
+            html_to_markdown(&html),
+            "## --- Day 1: Trebuchet?! ---
+
+Something is wrong with global snow production, and you've been selected to take a look. The Elves have even given you a [map](https://adventofcode.com/2023/day/1/map); on it, they've used stars to mark the top fifty locations.
+
+For example:
+
+```
+1abc2
+pqr3stu8vwx
+```
+
+In this example, the calibration values of these four lines are `12` and `38`. Adding these together produces `142`.
+
+- One & *two*
+
+## --- Part Two ---
+
+Your calculation isn't quite right.
+"
+        );
+    }
+}
@@ -0,0 +1,263 @@
+// Solving days using the inputs stored in the data directory of a year, as
+// dayNN.txt, or read from elsewhere. Solutions record the time spent in each phase.
+
+use std::{
+    io::{ErrorKind, Read},
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use crate::{
+    answer::Answer,
+    answers::{Answers, Check},
+    error::{Error, Result},
+    output::DayReport,
+    parallel,
+    params::Params,
+    solver::{get_solver, Parts, Registration, Solution},
+    visualize::Picture,
+    year::Year,
+    Day,
+};
+
+pub fn input_path(data_dir: &Path, day: Day) -> PathBuf {
+    data_dir.join(format!("day{:02}.txt", day.0))
+}
+
+/// Read the input of the day from the data directory
+pub fn load_input(data_dir: &Path, day: Day) -> Result<String> {
+    read_input(input_path(data_dir, day), day)
+}
+
+fn read_input(path: PathBuf, day: Day) -> Result<String> {
+    std::fs::read_to_string(&path).map_err(|e| {
+        if e.kind() == ErrorKind::NotFound {
+            Error::MissingInput { day, path }
+        } else {
+            Error::io(path, e)
+        }
+    })
+}
+
+/// Where the inputs of the days are read from
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InputSource {
+    /// dayNN.txt in the data directory of the year
+    DataDir(PathBuf),
+    /// A single file, holding the input of one day
+    File(PathBuf),
+    /// Standard input, holding the input of one day
+    Stdin,
+    /// A path in which `{day}` is replaced by the day, or `{day:02}` by the
+    /// day padded to two digits, e.g. `inputs/{day:02}.txt`
+    Pattern(String),
+}
+
+impl InputSource {
+    /// Check that the source can provide the inputs of `n_days` days
+    pub fn check(&self, n_days: usize) -> Result<()> {
+        match self {
+            InputSource::DataDir(dir) if !dir.is_dir() => Err(Error::MissingDataDir(dir.clone())),
+            InputSource::File(_) | InputSource::Stdin if n_days != 1 => Err(Error::Usage(
+                "An input file or standard input holds the input of a single day",
+            )),
+            InputSource::Pattern(p) if !p.contains("{day}") && !p.contains("{day:02}") => Err(
+                Error::Usage("An input pattern must contain {day} or {day:02}"),
+            ),
+            _ => Ok(()),
+        }
+    }
+
+    /// The path of the input of the day, or None for standard input
+    pub fn path(&self, day: Day) -> Option<PathBuf> {
+        match self {
+            InputSource::DataDir(dir) => Some(input_path(dir, day)),
+            InputSource::File(path) => Some(path.clone()),
+            InputSource::Stdin => None,
+            InputSource::Pattern(p) => Some(
+                p.replace("{day:02}", &format!("{:02}", day.0))
+                    .replace("{day}", &day.0.to_string())
+                    .into(),
+            ),
+        }
+    }
+
+    pub fn load(&self, day: Day) -> Result<String> {
+        match self.path(day) {
+            Some(path) => read_input(path, day),
+            None => {
+                let mut s = String::new();
+                std::io::stdin()
+                    .read_to_string(&mut s)
+                    .map_err(|e| Error::io("<stdin>", e))?;
+                Ok(s)
+            }
+        }
+    }
+}
+
+/// Solve the day with its default solver, using the input in the data directory
+///
+/// ```
+/// use advent2023::{runner, Answer, Day, Year};
+///
+/// let dir = std::env::temp_dir().join("advent2023-doctest-solve-day");
+/// std::fs::create_dir_all(&dir)?;
+/// std::fs::write(dir.join("day06.txt"), "Time: 7 15 30\nDistance: 9 40 200\n")?;
+/// let solution = runner::solve_day(&dir, Year(2023), Day(6))?;
+/// assert_eq!(solution.answer(1), Some(&Answer::Unsigned(288)));
+/// assert_eq!(solution.answer(2), Some(&Answer::Unsigned(71503)));
+/// # std::fs::remove_dir_all(&dir)?;
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+pub fn solve_day(data_dir: &Path, year: Year, day: Day) -> Result<Solution> {
+    solve_parts(data_dir, year, day, Parts::Both)
+}
+
+fn solve_parts(data_dir: &Path, year: Year, day: Day, parts: Parts) -> Result<Solution> {
+    let solver = get_solver(year, day).ok_or(Error::Unimplemented(day))?;
+    solver
+        .run_isolated(
+            &load_input(data_dir, day)?,
+            parts,
+            &solver.default_params(),
+            None,
+        )
+        .map_err(|failure| Error::failure(day, failure))
+}
+
+/// Draw the input of the day in the data directory, if the day supports it
+pub fn visualize_day(data_dir: &Path, year: Year, day: Day, picture: Picture) -> Result<String> {
+    let solver = get_solver(year, day).ok_or(Error::Unimplemented(day))?;
+    solver
+        .visualize(&load_input(data_dir, day)?, picture)
+        .map_err(|error| Error::BadInput { day, error })?
+        .ok_or(Error::NoVisualization(day))
+}
+
+/// Solve only one part (1 or 2) of the day with its default solver
+pub fn solve_part(data_dir: &Path, year: Year, day: Day, part: u8) -> Result<Answer> {
+    let solution = solve_parts(data_dir, year, day, Parts::Only(part))?;
+    solution
+        .result(part)
+        .cloned()
+        .expect("the requested part is solved")
+        .map_err(|error| Error::BadInput { day, error })
+}
+
+/// The parameters of each day with a solver, with the `assignments` of the
+/// parameters it declares. Each assignment must be to a parameter of at least
+/// one of the days.
+pub fn day_params(
+    days: &[(Day, Option<&Registration>)],
+    assignments: &[(String, String)],
+) -> Result<Vec<Option<Params>>> {
+    for (name, _) in assignments {
+        let declared = days
+            .iter()
+            .filter_map(|(_, solver)| *solver)
+            .any(|solver| solver.params.iter().any(|p| p.name == name));
+        if !declared {
+            return Err(Error::BadParam(format!(
+                "None of the selected days has a parameter named {name}. See `params <day>`"
+            )));
+        }
+    }
+    days.iter()
+        .map(|&(day, solver)| {
+            solver
+                .map(|solver| Params::new(solver.params, assignments))
+                .transpose()
+                .map_err(|reason| Error::BadParam(format!("Day {:02}: {reason}", day.0)))
+        })
+        .collect()
+}
+
+/// Solve the `parts` of each day that has a solver, using up to `jobs` threads,
+/// or one per CPU if `jobs` is 0. Parameters are set by `assignments`, see
+/// `day_params`. Days without a solver are reported as unimplemented, and their
+/// input is not read. Malformed inputs, panics, and solvers taking longer than
+/// `timeout` are reported per day; a missing input fails the whole run.
+pub fn solve_days(
+    source: &InputSource,
+    days: &[(Day, Option<&Registration>)],
+    parts: Parts,
+    assignments: &[(String, String)],
+    jobs: usize,
+    timeout: Option<Duration>,
+) -> Result<Vec<DayReport>> {
+    let params = day_params(days, assignments)?;
+    // Only check the source if we need to load any data
+    if days.iter().any(|x| x.1.is_some()) {
+        source.check(days.len())?;
+    }
+    let data = days
+        .iter()
+        .zip(params)
+        .map(|(&(day, solver), params)| {
+            let loaded = match solver.zip(params) {
+                None => None,
+                Some((solver, params)) => Some((solver, params, source.load(day)?)),
+            };
+            Ok((day, loaded))
+        })
+        .collect::<Result<Vec<_>>>()?;
+    let jobs = if jobs == 0 {
+        parallel::default_jobs()
+    } else {
+        jobs
+    };
+    Ok(parallel::map(&data, jobs, |(day, x)| DayReport {
+        day: *day,
+        solution: x
+            .as_ref()
+            .map(|(f, params, data)| f.run_isolated(data, parts, params, timeout)),
+        checks: None,
+    }))
+}
+
+/// Compare the answers of the solved days to the answers stored in the data
+/// directory. Returns the number of wrong answers. Parts that were not solved
+/// are unknown.
+pub fn check_reports(data_dir: &Path, reports: &mut [DayReport]) -> Result<usize> {
+    let mut n_wrong = 0;
+    for report in reports.iter_mut() {
+        if let Some(Ok(solution)) = &report.solution {
+            let answers = Answers::load(data_dir, report.day)?;
+            let checks = [1, 2].map(|part| match solution.answer(part) {
+                Some(answer) => answers.check(part, answer),
+                None => Check::Unknown,
+            });
+            n_wrong += checks
+                .iter()
+                .filter(|c| matches!(c, Check::Wrong { .. }))
+                .count();
+            report.checks = Some(checks);
+        }
+    }
+    Ok(n_wrong)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::InputSource;
+    use crate::{error::Error, Day};
+    use std::path::PathBuf;
+
+    #[test]
+    fn test_input_source() {
+        let pattern = InputSource::Pattern("inputs/{day:02}-{day}.txt".to_owned());
+        assert_eq!(pattern.path(Day(5)), Some(PathBuf::from("inputs/05-5.txt")));
+        assert!(pattern.check(3).is_ok());
+        assert!(matches!(
+            InputSource::Pattern("inputs/05.txt".to_owned()).check(1),
+            Err(Error::Usage(_))
+        ));
+        assert!(InputSource::Stdin.check(1).is_ok());
+        assert!(matches!(
+            InputSource::File("a.txt".into()).check(2),
+            Err(Error::Usage(_))
+        ));
+        assert_eq!(InputSource::Stdin.path(Day(5)), None);
+    }
+}
@@ -0,0 +1,186 @@
+// Parsing of day selections on the command line. A selection is a list of
+// tokens, separated by spaces or commas, each of which is one of:
+// * A day, e.g. `5`
+// * An inclusive range `1-10` or `1..=10`, or a half-open range `1..10`.
+//   Either end may be omitted, e.g. `12-` or `..5`
+// * `all`, `odd` or `even`
+// * `implemented`: Days with a registered solver
+// * `latest`: The most recently released day
+// * `unsolved`: Days where not both answers are stored in the data directory
+// Days are checked against the number of days in the year.
+
+use std::{path::Path, time::SystemTime};
+
+use crate::{
+    answers::Answers,
+    error::{Error, Result},
+    solver::registrations,
+    year::Year,
+    Day,
+};
+
+const FIRST: u8 = 1;
+
+fn parse_day(s: &str, last: u8) -> std::result::Result<u8, String> {
+    match s.parse::<u8>() {
+        Ok(n) if (FIRST..=last).contains(&n) => Ok(n),
+        Ok(n) => Err(format!("day {n} is not in {FIRST}-{last}")),
+        Err(_) => Err(format!("\"{s}\" is not a day")),
+    }
+}
+
+// Parse the token as a range, returning None if it is not a range
+fn parse_range(token: &str, last: u8) -> Option<std::result::Result<Vec<u8>, String>> {
+    if let Some((a, b)) = token.split_once("..=") {
+        Some(range_days(a, b, true, last))
+    } else if let Some((a, b)) = token.split_once("..") {
+        Some(range_days(a, b, false, last))
+    } else {
+        token
+            .split_once('-')
+            .map(|(a, b)| range_days(a, b, true, last))
+    }
+}
+
+fn range_days(
+    start: &str,
+    stop: &str,
+    inclusive: bool,
+    last: u8,
+) -> std::result::Result<Vec<u8>, String> {
+    let start = if start.is_empty() {
+        FIRST
+    } else {
+        parse_day(start, last)?
+    };
+    let stop = match (stop.is_empty(), inclusive) {
+        (true, _) => last,
+        (false, true) => parse_day(stop, last)?,
+        // Allow an exclusive end one past the last day
+        (false, false) => match stop.parse::<u8>() {
+            Ok(n) if n == last + 1 => last,
+            _ => parse_day(stop, last)? - 1,
+        },
+    };
+    if start > stop {
+        Err("range is empty".to_owned())
+    } else {
+        Ok((start..=stop).collect())
+    }
+}
+
+fn select_token(token: &str, year: Year, data_dir: Option<&Path>) -> Result<Vec<u8>> {
+    let last = year.days();
+    let bad = |reason: String| Error::BadSelection {
+        token: token.to_owned(),
+        reason,
+    };
+    let days = match token {
+        "all" => (FIRST..=last).collect(),
+        "odd" => (FIRST..=last).filter(|n| n % 2 == 1).collect(),
+        "even" => (FIRST..=last).filter(|n| n % 2 == 0).collect(),
+        "implemented" => registrations()
+            .iter()
+            .filter(|r| r.year == year)
+            .map(|r| r.day.0)
+            .collect(),
+        "latest" => match year.latest_day(SystemTime::now()) {
+            Some(day) => vec![day.0],
+            None => return Err(bad("no days are released yet".to_owned())),
+        },
+        "unsolved" => {
+            let Some(data_dir) = data_dir else {
+                return Err(bad(
+                    "the answers are stored in the data directory, which is not given".to_owned(),
+                ));
+            };
+            let mut v = Vec::new();
+            for n in FIRST..=last {
+                let answers = Answers::load(data_dir, Day(n))?;
+                if answers.0.iter().any(Option::is_none) {
+                    v.push(n)
+                }
+            }
+            v
+        }
+        _ => match parse_range(token, last) {
+            Some(range) => range.map_err(bad)?,
+            None => vec![parse_day(token, last).map_err(bad)?],
+        },
+    };
+    Ok(days)
+}
+
+/// Parse the day selection into a sorted list of unique days of the year.
+/// `data_dir` is the data directory of the year, if there is one.
+pub fn select_days<T: AsRef<str>>(
+    v: &[T],
+    year: Year,
+    data_dir: Option<&Path>,
+) -> Result<Vec<Day>> {
+    let mut days = Vec::new();
+    for token in v
+        .iter()
+        .flat_map(|s| s.as_ref().split([',', ' ']))
+        .filter(|s| !s.is_empty())
+    {
+        days.extend(select_token(token, year, data_dir)?.into_iter().map(Day));
+    }
+    days.sort_unstable();
+    days.dedup();
+    Ok(days)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::select_days;
+    use crate::{error::Error, year::Year};
+    use std::path::Path;
+
+    fn select(s: &str) -> Vec<u8> {
+        let days = select_days(&[s], Year(2023), Some(Path::new("nonexistent"))).unwrap();
+        days.into_iter().map(|d| d.0).collect()
+    }
+
+    #[test]
+    fn test_select() {
+        assert_eq!(select("5"), vec![5]);
+        assert_eq!(select("1-3"), vec![1, 2, 3]);
+        assert_eq!(select("1..=3,8, 24-"), vec![1, 2, 3, 8, 24, 25]);
+        assert_eq!(select("1..3"), vec![1, 2]);
+        assert_eq!(select("..=2 25.."), vec![1, 2, 25]);
+        assert_eq!(select("23..26"), vec![23, 24, 25]);
+        assert_eq!(select("3,odd").len(), 13);
+        assert_eq!(select("even").len(), 12);
+        assert_eq!(select("all").len(), 25);
+        assert_eq!(select("4,4,1"), vec![1, 4]);
+        assert!(select("implemented").contains(&1));
+        // Without any stored answers, all days are unsolved
+        assert_eq!(select("unsolved").len(), 25);
+        // Later years are shorter
+        let days = select_days(&["all"], Year(2025), None).unwrap();
+        assert_eq!(days.len(), 12);
+        assert!(select_days(&["13"], Year(2025), None).is_err());
+    }
+
+    #[test]
+    fn test_bad_selection() {
+        for (s, bad_token) in [
+            ("1,x", "x"),
+            ("0", "0"),
+            ("26", "26"),
+            ("5-3", "5-3"),
+            ("3-30", "3-30"),
+        ] {
+            match select_days(&[s], Year(2023), Some(Path::new("nonexistent"))) {
+                Err(Error::BadSelection { token, .. }) => assert_eq!(token, bad_token),
+                _ => panic!("Selection \"{s}\" should be invalid"),
+            }
+        }
+        // Without a data directory, there are no answers to look at
+        assert!(matches!(
+            select_days(&["unsolved"], Year(2023), None),
+            Err(Error::BadSelection { .. })
+        ));
+    }
+}
@@ -0,0 +1,316 @@
+use std::{
+    fmt::Display,
+    time::{Duration, Instant},
+};
+
+use crate::{
+    answer::{Answer, IntoAnswer},
+    input::{Layout, ParseError, ParseResult},
+    isolate,
+    params::{Param, Params},
+    visualize::Picture,
+    year::Year,
+    Day,
+};
+
+/// A solution to a single day. To add a day, implement this trait for a unit
+/// struct, and register it with `inventory::submit! { Registration::new::<T>() }`.
+pub trait Solver {
+    /// The input after parsing, shared between the two parts
+    type Parsed;
+    /// The answer types, e.g. `usize`. A part that is not solved yet can use
+    /// `answer::Unimplemented`, which is reported as pending. A part that can
+    /// find the input malformed returns a `ParseResult`, e.g. `ParseResult<usize>`.
+    type Answer1: IntoAnswer;
+    type Answer2: IntoAnswer;
+
+    const YEAR: u16;
+    const DAY: u8;
+    const TITLE: &'static str;
+    /// Name of this solver, to distinguish multiple solvers of the same day
+    const VARIANT: &'static str = DEFAULT_VARIANT;
+    /// Values fixed by the puzzle, which can be changed with `--param name=value`
+    const PARAMS: &'static [Param] = &[];
+    /// How the input is laid out, which decides how it is prepared for `parse`
+    const LAYOUT: Layout = Layout::Lines;
+
+    /// Parse the input prepared for `LAYOUT`, or report where it is malformed
+    fn parse(s: &str) -> ParseResult<Self::Parsed>;
+
+    /// Parse the input with a value for each of `PARAMS`. Days with parameters
+    /// implement this, and `parse` by calling it with `Params::defaults(Self::PARAMS)`.
+    fn parse_with(s: &str, _params: &Params) -> ParseResult<Self::Parsed> {
+        Self::parse(s)
+    }
+
+    fn part1(parsed: &Self::Parsed) -> Self::Answer1;
+    fn part2(parsed: &Self::Parsed) -> Self::Answer2;
+
+    /// Parse the input and solve both parts
+    ///
+    /// ```
+    /// use advent2023::{days::day09::Day09, Solver};
+    ///
+    /// assert_eq!(Day09::solve("0 3 6 9 12 15\n1 3 6 10 15 21\n")?, (46, -3));
+    /// # Ok::<(), advent2023::input::ParseError>(())
+    /// ```
+    fn solve(s: &str) -> ParseResult<(Self::Answer1, Self::Answer2)> {
+        let parsed = Self::parse(&Self::LAYOUT.prepare(s)?)?;
+        Ok((Self::part1(&parsed), Self::part2(&parsed)))
+    }
+
+    /// Draw the parsed input, or None if the day has no visualization
+    fn visualize(_parsed: &Self::Parsed, _picture: Picture) -> Option<String> {
+        None
+    }
+}
+
+pub const DEFAULT_VARIANT: &str = "default";
+
+/// Time spent in each phase of solving a day
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Timings {
+    pub parse: Duration,
+    pub part1: Duration,
+    pub part2: Duration,
+}
+
+impl Timings {
+    pub fn total(&self) -> Duration {
+        self.parse + self.part1 + self.part2
+    }
+}
+
+/// Which parts of a day to solve
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Parts {
+    #[default]
+    Both,
+    /// Only part 1 or 2
+    Only(u8),
+}
+
+impl Parts {
+    pub fn includes(self, part: u8) -> bool {
+        match self {
+            Parts::Both => true,
+            Parts::Only(only) => only == part,
+        }
+    }
+}
+
+/// Both parts if None, else only the given part
+impl From<Option<u8>> for Parts {
+    fn from(part: Option<u8>) -> Self {
+        part.map_or(Parts::Both, Parts::Only)
+    }
+}
+
+/// The answers of a day, and the time spent on them. Parts that were not asked
+/// for have no result, and take no time. A part that finds the input malformed
+/// has an error, without affecting the other part.
+pub struct Solution {
+    pub timings: Timings,
+    pub part1: Option<ParseResult<Answer>>,
+    pub part2: Option<ParseResult<Answer>>,
+}
+
+impl Solution {
+    /// The result of part 1 or 2, if it was asked for
+    pub fn result(&self, part: u8) -> Option<&ParseResult<Answer>> {
+        if part == 1 {
+            self.part1.as_ref()
+        } else {
+            self.part2.as_ref()
+        }
+    }
+
+    /// The answer of part 1 or 2, if it was solved
+    pub fn answer(&self, part: u8) -> Option<&Answer> {
+        self.result(part)?.as_ref().ok()
+    }
+
+    /// The first error of a part, if any
+    pub fn error(&self) -> Option<&ParseError> {
+        [&self.part1, &self.part2]
+            .into_iter()
+            .find_map(|r| r.as_ref()?.as_ref().err())
+    }
+}
+
+/// Why solving a day gave no solution
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Failure {
+    /// The input is malformed
+    BadInput(ParseError),
+    /// The solver panicked, with the message and location of the panic
+    Panicked(String),
+    /// The solver did not finish within the time limit
+    TimedOut(Duration),
+}
+
+impl Display for Failure {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Failure::BadInput(error) => write!(f, "{error}"),
+            Failure::Panicked(message) => write!(f, "solver panicked: {message}"),
+            Failure::TimedOut(timeout) => write!(f, "solver timed out after {timeout:?}"),
+        }
+    }
+}
+
+impl From<ParseError> for Failure {
+    fn from(error: ParseError) -> Self {
+        Failure::BadInput(error)
+    }
+}
+
+/// A type-erased `Solver`, collected in the registry
+pub struct Registration {
+    pub year: Year,
+    pub day: Day,
+    pub title: &'static str,
+    pub variant: &'static str,
+    pub params: &'static [Param],
+    runner: fn(&str, Parts, &Params) -> ParseResult<Solution>,
+    visualizer: fn(&str, Picture) -> ParseResult<Option<String>>,
+}
+
+inventory::collect!(Registration);
+
+impl Registration {
+    pub const fn new<S: Solver>() -> Self {
+        Registration {
+            year: Year(S::YEAR),
+            day: Day(S::DAY),
+            title: S::TITLE,
+            variant: S::VARIANT,
+            params: S::PARAMS,
+            runner: run::<S>,
+            visualizer: visualize::<S>,
+        }
+    }
+
+    /// The parameters of the day with their default values
+    pub fn default_params(&self) -> Params {
+        Params::defaults(self.params)
+    }
+
+    /// Solve both parts of the input, timing each phase separately
+    pub fn run(&self, s: &str) -> ParseResult<Solution> {
+        (self.runner)(s, Parts::Both, &self.default_params())
+    }
+
+    /// Solve the given parts of the input with the given parameter values. The
+    /// input is parsed once, also when solving both parts.
+    pub fn run_parts(&self, s: &str, parts: Parts, params: &Params) -> ParseResult<Solution> {
+        (self.runner)(s, parts, params)
+    }
+
+    /// Solve the input like `run_parts`, but report a panic of the solver as a
+    /// failure instead of unwinding. With a `timeout`, the solver runs on its own
+    /// thread, which is given up on if it takes longer.
+    pub fn run_isolated(
+        &self,
+        s: &str,
+        parts: Parts,
+        params: &Params,
+        timeout: Option<Duration>,
+    ) -> Result<Solution, Failure> {
+        let runner = self.runner;
+        let result = match timeout {
+            None => isolate::catch(|| runner(s, parts, params))?,
+            Some(timeout) => {
+                let (s, params) = (s.to_owned(), params.clone());
+                isolate::catch_with_timeout(move || runner(&s, parts, &params), timeout)?
+            }
+        };
+        Ok(result?)
+    }
+
+    /// Solve only one part (1 or 2) of the input with the given parameter values.
+    /// Examples often only apply to one part.
+    pub fn run_part(&self, s: &str, part: u8, params: &Params) -> ParseResult<Answer> {
+        let solution = self.run_parts(s, Parts::Only(part), params)?;
+        solution
+            .result(part)
+            .cloned()
+            .expect("the requested part is solved")
+    }
+
+    /// Draw the input, or None if the day has no visualization
+    pub fn visualize(&self, s: &str, picture: Picture) -> ParseResult<Option<String>> {
+        (self.visualizer)(s, picture)
+    }
+}
+
+fn visualize<S: Solver>(s: &str, picture: Picture) -> ParseResult<Option<String>> {
+    Ok(S::visualize(&S::parse(&S::LAYOUT.prepare(s)?)?, picture))
+}
+
+// Time one part if it is included, converting the answer afterwards
+fn time_part<A: IntoAnswer>(
+    included: bool,
+    f: impl FnOnce() -> A,
+) -> (Duration, Option<ParseResult<Answer>>) {
+    if !included {
+        return (Duration::ZERO, None);
+    }
+    let start = Instant::now();
+    let answer = f();
+    let time = start.elapsed();
+    (time, Some(answer.into_answer()))
+}
+
+fn run<S: Solver>(s: &str, parts: Parts, params: &Params) -> ParseResult<Solution> {
+    let start = Instant::now();
+    let parsed = S::parse_with(&S::LAYOUT.prepare(s)?, params)?;
+    let parse = start.elapsed();
+    let (part1, a) = time_part(parts.includes(1), || S::part1(&parsed));
+    let (part2, b) = time_part(parts.includes(2), || S::part2(&parsed));
+    Ok(Solution {
+        timings: Timings {
+            parse,
+            part1,
+            part2,
+        },
+        part1: a,
+        part2: b,
+    })
+}
+
+/// All registered solvers, sorted by year and day, with the default variant first
+pub fn registrations() -> Vec<&'static Registration> {
+    let mut v: Vec<_> = inventory::iter::<Registration>().collect();
+    v.sort_unstable_by_key(|r| (r.year, r.day, r.variant != DEFAULT_VARIANT, r.variant));
+    v
+}
+
+/// Get the default solver of the day, or None if the day is not implemented
+pub fn get_solver(year: Year, day: Day) -> Option<&'static Registration> {
+    registrations()
+        .into_iter()
+        .find(|r| r.year == year && r.day == day)
+}
+
+/// Get a specific variant of the solver of the day
+pub fn get_variant(year: Year, day: Day, variant: &str) -> Option<&'static Registration> {
+    inventory::iter::<Registration>()
+        .find(|r| r.year == year && r.day == day && r.variant == variant)
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn test_registrations() {
+        let v = super::registrations();
+        assert!(!v.is_empty());
+        for r in v.iter() {
+            assert!(r.year.check_day(r.day).is_ok());
+        }
+        // No two solvers may share year, day and variant
+        for w in v.windows(2) {
+            assert!((w[0].year, w[0].day, w[0].variant) != (w[1].year, w[1].day, w[1].variant));
+        }
+    }
+}
@@ -0,0 +1,222 @@
+use std::{fmt::Display, path::Path, time::Duration};
+
+use crate::{
+    answer::Answer,
+    answers::Answers,
+    client::AocClient,
+    error::{Error, Result},
+    year::Year,
+    Day,
+};
+
+/// The server's verdict on a submitted answer
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Outcome {
+    Correct,
+    TooHigh,
+    TooLow,
+    Wrong,
+    /// An answer was submitted too recently. Contains the time left to wait, if known.
+    RateLimited(Option<Duration>),
+    /// This part is already solved, or is not yet unlocked
+    AlreadySolved,
+    /// The response could not be understood. Contains the text of the response.
+    Unknown(String),
+}
+
+impl Display for Outcome {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Outcome::Correct => f.write_str("That's the right answer!"),
+            Outcome::TooHigh => f.write_str("That's not the right answer: Your answer is too high"),
+            Outcome::TooLow => f.write_str("That's not the right answer: Your answer is too low"),
+            Outcome::Wrong => f.write_str("That's not the right answer"),
+            Outcome::RateLimited(None) => f.write_str("You gave an answer too recently"),
+            Outcome::RateLimited(Some(d)) => write!(
+                f,
+                "You gave an answer too recently. Wait {}s before trying again",
+                d.as_secs()
+            ),
+            Outcome::AlreadySolved => {
+                f.write_str("You don't seem to be solving the right level. Is it already solved?")
+            }
+            Outcome::Unknown(s) => write!(f, "Unknown response from server:\n{s}"),
+        }
+    }
+}
+
+// Get the text content of the <article> element of the page, with tags removed
+// and whitespace collapsed.
+fn article_text(html: &str) -> &str {
+    let start = html.find("<article").unwrap_or(0);
+    let end = html[start..]
+        .find("</article>")
+        .map_or(html.len(), |i| start + i);
+    &html[start..end]
+}
+
+fn strip_tags(html: &str) -> String {
+    let mut result = String::with_capacity(html.len());
+    let mut in_tag = false;
+    for c in html.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' => in_tag = false,
+            c if !in_tag => result.push(c),
+            _ => (),
+        }
+    }
+    result.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+// Parse e.g. "You have 1m 5s left to wait"
+fn parse_wait(text: &str) -> Option<Duration> {
+    let start = text.find("You have ")? + "You have ".len();
+    let end = start + text[start..].find(" left to wait")?;
+    let mut seconds = 0;
+    for token in text[start..end].split_whitespace() {
+        let (n, multiplier) = if let Some(n) = token.strip_suffix('h') {
+            (n, 3600)
+        } else if let Some(n) = token.strip_suffix('m') {
+            (n, 60)
+        } else if let Some(n) = token.strip_suffix('s') {
+            (n, 1)
+        } else {
+            return None;
+        };
+        seconds += multiplier * n.parse::<u64>().ok()?;
+    }
+    Some(Duration::from_secs(seconds))
+}
+
+pub fn parse_response(html: &str) -> Outcome {
+    let text = strip_tags(article_text(html));
+    if text.contains("That's the right answer") {
+        Outcome::Correct
+    } else if text.contains("That's not the right answer") {
+        if text.contains("your answer is too high") {
+            Outcome::TooHigh
+        } else if text.contains("your answer is too low") {
+            Outcome::TooLow
+        } else {
+            Outcome::Wrong
+        }
+    } else if text.contains("You gave an answer too recently") {
+        Outcome::RateLimited(parse_wait(&text))
+    } else if text.contains("You don't seem to be solving the right level") {
+        Outcome::AlreadySolved
+    } else {
+        Outcome::Unknown(text)
+    }
+}
+
+/// Submit an answer to one part of the day. If the answer is correct, it is
+/// stored in the data directory. A pending answer is not submitted.
+pub fn submit(
+    client: &AocClient,
+    data_dir: &Path,
+    year: Year,
+    day: Day,
+    part: u8,
+    answer: &Answer,
+) -> Result<Outcome> {
+    if !answer.is_implemented() {
+        return Err(Error::UnimplementedPart { day, part });
+    }
+    let answer = answer.to_string();
+    let outcome = parse_response(&client.submit(year, day, part, &answer)?);
+    if outcome == Outcome::Correct && data_dir.is_dir() {
+        Answers::record(data_dir, day, part, &answer)?;
+    }
+    Ok(outcome)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{parse_response, submit, Outcome};
+    use crate::{
+        answer::Answer,
+        answers::Answers,
+        client::{AocClient, ClientOptions},
+        error::Error,
+        testserver::TestServer,
+        year::Year,
+        Day,
+    };
+    use std::time::Duration;
+
+    fn page(article: &str) -> String {
+        format!("<html><body><main><article><p>{article}</p></article></main></body></html>")
+    }
+
+    #[test]
+    fn test_parse_response() {
+        let cases = [
+            (
+                "That's the right answer!  You are <span class=\"day-success\">one gold star</span> closer.",
+                Outcome::Correct,
+            ),
+            (
+                "That's not the right answer; your answer is too high.  If you're stuck, [...] please wait one minute before trying again.",
+                Outcome::TooHigh,
+            ),
+            (
+                "That's not the right answer; your answer is too low.",
+                Outcome::TooLow,
+            ),
+            (
+                "That's not the right answer.  If you're stuck, make sure you're using the full input data",
+                Outcome::Wrong,
+            ),
+            (
+                "You gave an answer too recently; you have to wait after submitting an answer before trying again.  You have 1m 5s left to wait. <a href=\"/2023/day/1\">[Return to Day 1]</a>",
+                Outcome::RateLimited(Some(Duration::from_secs(65))),
+            ),
+            (
+                "You don't seem to be solving the right level.  Did you already complete it? <a href=\"/2023/day/1\">[Return to Day 1]</a>",
+                Outcome::AlreadySolved,
+            ),
+            ("Something else", Outcome::Unknown("Something else".to_owned())),
+        ];
+        for (article, outcome) in cases {
+            assert_eq!(parse_response(&page(article)), outcome);
+        }
+    }
+
+    #[test]
+    fn test_submit() {
+        let server = TestServer::new(vec![(200, page("That's the right answer!"))]);
+        let client = AocClient::new("abc", &server.url, ClientOptions::default()).unwrap();
+        let data_dir =
+            std::env::temp_dir().join(format!("advent2023-submit-{}", std::process::id()));
+        std::fs::create_dir_all(&data_dir).unwrap();
+        let pending = submit(
+            &client,
+            &data_dir,
+            Year(2022),
+            Day(4),
+            1,
+            &Answer::Unimplemented,
+        );
+        assert!(matches!(pending, Err(Error::UnimplementedPart { .. })));
+        let outcome = submit(
+            &client,
+            &data_dir,
+            Year(2022),
+            Day(4),
+            2,
+            &Answer::Unsigned(123),
+        );
+        let outcome = outcome.unwrap();
+        assert_eq!(outcome, Outcome::Correct);
+        let answers = Answers::load(&data_dir, Day(4)).unwrap();
+        std::fs::remove_dir_all(&data_dir).unwrap();
+        assert_eq!(answers, Answers([None, Some("123".to_owned())]));
+
+        let requests = server.join();
+        assert_eq!(requests.len(), 1);
+        assert!(requests[0].starts_with("POST /2022/day/4/answer "));
+        assert!(requests[0].contains("session=abc"));
+        assert!(requests[0].ends_with("level=2&answer=123"));
+    }
+}
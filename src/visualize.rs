@@ -0,0 +1,102 @@
+// Pictures of the input of a day, for debugging. Days opt in by implementing
+// `Solver::visualize`; these are the shared pieces for drawing them.
+
+use std::fmt::{Display, Write};
+
+/// The kind of picture to draw
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Picture {
+    /// Text for the terminal, optionally colored with ANSI escape codes
+    Text { color: bool },
+    /// A standalone SVG image
+    Svg,
+}
+
+pub const BOLD_YELLOW: &str = "\x1b[1;33m";
+pub const BOLD_RED: &str = "\x1b[1;31m";
+pub const GREEN: &str = "\x1b[32m";
+pub const DIM: &str = "\x1b[2m";
+const RESET: &str = "\x1b[0m";
+
+/// Wrap `text` in the ANSI `style` if `color` is set
+pub fn paint(text: impl Display, style: &str, color: bool) -> String {
+    if color {
+        format!("{style}{text}{RESET}")
+    } else {
+        text.to_string()
+    }
+}
+
+/// A minimal SVG writer. Coordinates are in pixels from the top left.
+pub struct Svg {
+    width: f64,
+    height: f64,
+    body: String,
+}
+
+impl Svg {
+    pub fn new(width: f64, height: f64) -> Self {
+        Svg {
+            width,
+            height,
+            body: String::new(),
+        }
+    }
+
+    pub fn rect(&mut self, x: f64, y: f64, width: f64, height: f64, fill: &str) {
+        writeln!(
+            self.body,
+            r#"<rect x="{x}" y="{y}" width="{width}" height="{height}" fill="{fill}"/>"#
+        )
+        .unwrap()
+    }
+
+    pub fn line(&mut self, from: (f64, f64), to: (f64, f64), stroke: &str, width: f64) {
+        writeln!(
+            self.body,
+            r#"<line x1="{}" y1="{}" x2="{}" y2="{}" stroke="{stroke}" stroke-width="{width}"/>"#,
+            from.0, from.1, to.0, to.1
+        )
+        .unwrap()
+    }
+
+    /// A closed outline through the points
+    pub fn polygon(&mut self, points: &[(f64, f64)], stroke: &str, width: f64) {
+        let points: Vec<_> = points.iter().map(|(x, y)| format!("{x},{y}")).collect();
+        writeln!(
+            self.body,
+            r#"<polygon points="{}" fill="none" stroke="{stroke}" stroke-width="{width}" stroke-linejoin="round"/>"#,
+            points.join(" ")
+        )
+        .unwrap()
+    }
+
+    pub fn finish(self) -> String {
+        format!(
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{0}\" height=\"{1}\" viewBox=\"0 0 {0} {1}\">\n{2}</svg>\n",
+            self.width, self.height, self.body
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{paint, Svg, GREEN};
+
+    #[test]
+    fn test_svg() {
+        let mut svg = Svg::new(20.0, 10.0);
+        svg.rect(0.0, 0.0, 10.0, 10.0, "green");
+        svg.polygon(&[(0.0, 0.0), (5.0, 2.5)], "red", 1.5);
+        assert_eq!(
+            svg.finish(),
+            r#"<svg xmlns="http://www.w3.org/2000/svg" width="20" height="10" viewBox="0 0 20 10">
+<rect x="0" y="0" width="10" height="10" fill="green"/>
+<polygon points="0,0 5,2.5" fill="none" stroke="red" stroke-width="1.5" stroke-linejoin="round"/>
+</svg>
+"#
+        );
+        assert_eq!(paint('x', GREEN, false), "x");
+        assert_eq!(paint('x', GREEN, true), "\x1b[32mx\x1b[0m");
+    }
+}
@@ -0,0 +1,185 @@
+// `solve --watch`: solve again whenever an input or example changes. Files are
+// polled for their modification times, which is plenty for a handful of files.
+
+use std::{
+    collections::HashMap,
+    io::IsTerminal,
+    path::PathBuf,
+    thread,
+    time::{Duration, SystemTime},
+};
+
+use advent2023::{
+    answer::Answer,
+    answers::answers_path,
+    error::{Error, Result},
+    examples,
+    output::DayReport,
+    runner::InputSource,
+    Day,
+};
+
+use crate::{render_example_results, SolveRun};
+
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+// The watched files with their modification times, None if missing
+type Snapshot = Vec<(PathBuf, Option<SystemTime>)>;
+
+// The answers of the last run, by day and part
+type Answers = HashMap<(Day, u8), Answer>;
+
+/// Solve the days of `run` until interrupted, each time one of their files changes
+pub fn watch(run: &SolveRun) -> Result<()> {
+    if let InputSource::Stdin = run.source {
+        return Err(Error::Usage("Standard input cannot be watched"));
+    }
+    // Fail at once on a missing data directory, rather than watching nothing
+    run.source.check(implemented_days(run).count())?;
+    let mut previous = Answers::new();
+    let mut snapshot = take_snapshot(run);
+    loop {
+        if std::io::stdout().is_terminal() {
+            // Clear the screen and move to the top left
+            print!("\x1b[2J\x1b[H");
+        }
+        println!(
+            "Watching {} file(s), press Ctrl-C to stop\n",
+            snapshot.len()
+        );
+        if let Err(e) = run_once(run, &mut previous) {
+            println!("Error: {e}");
+        }
+
+        // Wait for a change. Examples may be added in the meantime, so the
+        // files are listed again each time.
+        loop {
+            thread::sleep(POLL_INTERVAL);
+            let next = take_snapshot(run);
+            if next != snapshot {
+                snapshot = next;
+                break;
+            }
+        }
+    }
+}
+
+// Solve the examples, and the inputs if all examples pass
+fn run_once(run: &SolveRun, previous: &mut Answers) -> Result<()> {
+    if let Some(data_dir) = &run.data_dir {
+        let results = examples::run_examples(data_dir, &run.days, run.parts, &run.params)?;
+        let (output, n_wrong) = render_example_results(&results);
+        print!("{output}");
+        if n_wrong > 0 {
+            println!("\n{n_wrong} wrong example answer(s), not solving the inputs");
+            return Ok(());
+        }
+        if !results.is_empty() {
+            println!();
+        }
+    }
+    let (reports, output, _) = run.run()?;
+    print!("{output}");
+    let changes = answer_changes(previous, &reports);
+    if !changes.is_empty() {
+        println!("Changed since the last run:");
+        for change in changes {
+            println!("  {change}");
+        }
+    }
+    *previous = answers(&reports);
+    Ok(())
+}
+
+fn implemented_days(run: &SolveRun) -> impl Iterator<Item = Day> + '_ {
+    run.days
+        .iter()
+        .filter(|(_, solver)| solver.is_some())
+        .map(|&(day, _)| day)
+}
+
+fn take_snapshot(run: &SolveRun) -> Snapshot {
+    let mut paths = Vec::new();
+    for day in implemented_days(run) {
+        paths.extend(run.source.path(day));
+        if let Some(data_dir) = &run.data_dir {
+            if run.check {
+                paths.push(answers_path(data_dir, day));
+            }
+            for (input, answers) in examples::saved_examples(data_dir, day) {
+                paths.push(input);
+                paths.push(answers);
+            }
+        }
+    }
+    paths
+        .into_iter()
+        .map(|path| {
+            let modified = path.metadata().and_then(|m| m.modified()).ok();
+            (path, modified)
+        })
+        .collect()
+}
+
+fn answers(reports: &[DayReport]) -> Answers {
+    let mut answers = Answers::new();
+    for report in reports {
+        if let Some(Ok(solution)) = &report.solution {
+            for part in [1, 2] {
+                if let Some(answer) = solution.answer(part) {
+                    answers.insert((report.day, part), answer.clone());
+                }
+            }
+        }
+    }
+    answers
+}
+
+// The answers that differ from those of the previous run. Answers that are
+// new, such as on the first run, are not changes.
+fn answer_changes(previous: &Answers, reports: &[DayReport]) -> Vec<String> {
+    let current = answers(reports);
+    let mut keys: Vec<_> = current.keys().collect();
+    keys.sort();
+    keys.into_iter()
+        .filter_map(|key| {
+            let old = previous.get(key)?;
+            let new = &current[key];
+            (old != new).then(|| format!("Day {:02} part {}: {old} -> {new}", key.0 .0, key.1))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use advent2023::{
+        output::DayReport,
+        solver::{Solution, Timings},
+        Day,
+    };
+
+    use super::{answer_changes, answers};
+
+    fn report(day: u8, part1: u64, part2: u64) -> DayReport {
+        DayReport {
+            day: Day(day),
+            solution: Some(Ok(Solution {
+                timings: Timings::default(),
+                part1: Some(Ok(part1.into())),
+                part2: Some(Ok(part2.into())),
+            })),
+            checks: None,
+        }
+    }
+
+    #[test]
+    fn test_answer_changes() {
+        let first = [report(6, 288, 71503)];
+        assert!(answer_changes(&answers(&[]), &first).is_empty());
+        let second = [report(6, 289, 71503), report(7, 1, 2)];
+        assert_eq!(
+            answer_changes(&answers(&first), &second),
+            ["Day 06 part 1: 288 -> 289"]
+        );
+    }
+}
@@ -0,0 +1,242 @@
+// Advent of Code has run every December since 2015. Until 2024 each year had
+// 25 days; from 2025 on there are 12. Data of each year is stored in its own
+// subdirectory of the data directory, e.g. data/2023/day05.txt.
+
+use std::{
+    fmt::Display,
+    path::{Path, PathBuf},
+    str::FromStr,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use crate::{
+    error::{Error, Result},
+    Day,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Year(pub u16);
+
+/// The year used when no `--year` is given
+pub const DEFAULT_YEAR: Year = Year(2023);
+
+// Before years got their own directory, the data of 2023 was stored directly
+// in the data directory
+const FLAT_LAYOUT_YEAR: Year = Year(2023);
+
+const FIRST_YEAR: u16 = 2015;
+
+impl FromStr for Year {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.parse::<u16>() {
+            Ok(n) if n >= FIRST_YEAR => Ok(Year(n)),
+            _ => Err(Error::BadYear(s.to_owned())),
+        }
+    }
+}
+
+impl Display for Year {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+// Number of days between 1970-01-01 and the given date, using the algorithm
+// from http://howardhinnant.github.io/date_algorithms.html#days_from_civil
+fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let day_of_year = (153 * ((month + 9) % 12) + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146097 + day_of_era - 719468
+}
+
+impl Year {
+    /// Number of days in the event of this year
+    pub fn days(self) -> u8 {
+        if self.0 >= 2025 {
+            12
+        } else {
+            25
+        }
+    }
+
+    /// Check that the day is part of the event of this year
+    pub fn check_day(self, day: Day) -> Result<Day> {
+        if (1..=self.days()).contains(&day.0) {
+            Ok(day)
+        } else {
+            Err(Error::NoSuchDay { year: self, day })
+        }
+    }
+
+    /// The time the puzzle of the day unlocks: midnight EST, i.e. 05:00 UTC
+    pub fn release(self, day: Day) -> SystemTime {
+        let days = days_from_civil(self.0.into(), 12, day.0.into());
+        let seconds = days as u64 * 24 * 60 * 60 + 5 * 60 * 60;
+        UNIX_EPOCH + Duration::from_secs(seconds)
+    }
+
+    pub fn is_released(self, day: Day, now: SystemTime) -> bool {
+        self.release(day) <= now
+    }
+
+    /// The most recently released day, or None if no days are released
+    pub fn latest_day(self, now: SystemTime) -> Option<Day> {
+        (1..=self.days())
+            .rev()
+            .map(Day)
+            .find(|&day| self.is_released(day, now))
+    }
+
+    /// The directory holding the data of this year
+    pub fn data_dir(self, data_dir: &Path) -> PathBuf {
+        data_dir.join(self.to_string())
+    }
+
+    /// Move data stored in the old flat layout, directly in `data_dir`, into
+    /// the directory of its year. Returns the number of files moved. The files
+    /// are gathered in a temporary directory, which is renamed once all files
+    /// are in it, so the directory of the year never holds part of the data.
+    /// If a file cannot be moved, the files moved so far are moved back; if
+    /// even that fails, the next migration picks up where this one stopped.
+    pub fn migrate_flat_layout(self, data_dir: &Path) -> std::io::Result<usize> {
+        let dir = self.data_dir(data_dir);
+        if self != FLAT_LAYOUT_YEAR || dir.exists() {
+            return Ok(0);
+        }
+        let partial = data_dir.join(format!("{self}.partial"));
+        let flat_files = flat_files(data_dir);
+        if flat_files.is_empty() && !partial.is_dir() {
+            return Ok(0);
+        }
+        std::fs::create_dir_all(&partial)?;
+        let mut moved = Vec::new();
+        for path in &flat_files {
+            // The file names were checked by `flat_files`
+            let target = partial.join(path.file_name().unwrap());
+            if let Err(e) = std::fs::rename(path, &target) {
+                undo(&moved, &partial);
+                return Err(e);
+            }
+            moved.push((path, target));
+        }
+        let n_files = std::fs::read_dir(&partial)?.count();
+        std::fs::rename(&partial, &dir)?;
+        Ok(n_files)
+    }
+}
+
+// Move the files of a failed migration back, and remove the temporary
+// directory if that empties it
+fn undo(moved: &[(&PathBuf, PathBuf)], partial: &Path) {
+    for (path, target) in moved.iter().rev() {
+        let _ = std::fs::rename(target, path);
+    }
+    let _ = std::fs::remove_dir(partial);
+}
+
+// The files of the flat layout: dayNN.txt, dayNN.answers, dayNN.html and dayNN.md
+fn flat_files(data_dir: &Path) -> Vec<PathBuf> {
+    let Ok(entries) = std::fs::read_dir(data_dir) else {
+        return Vec::new();
+    };
+    entries
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+        .filter(|path| {
+            path.is_file()
+                && path
+                    .file_name()
+                    .and_then(|name| name.to_str())
+                    .and_then(|name| name.strip_prefix("day"))
+                    .and_then(|name| name.split_once('.'))
+                    .is_some_and(|(day, extension)| {
+                        day.len() == 2
+                            && day.bytes().all(|b| b.is_ascii_digit())
+                            && matches!(extension, "txt" | "answers" | "html" | "md")
+                    })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Year;
+    use crate::Day;
+    use std::time::{Duration, UNIX_EPOCH};
+
+    #[test]
+    fn test_year() {
+        assert!("2014".parse::<Year>().is_err());
+        assert!("abc".parse::<Year>().is_err());
+        assert_eq!("2015".parse::<Year>().unwrap(), Year(2015));
+        assert_eq!(Year(2024).days(), 25);
+        assert_eq!(Year(2025).days(), 12);
+        assert!(Year(2025).check_day(Day(13)).is_err());
+        assert!(Year(2024).check_day(Day(13)).is_ok());
+    }
+
+    #[test]
+    fn test_release() {
+        // 2023-12-01T05:00:00Z
+        let release = UNIX_EPOCH + Duration::from_secs(1701406800);
+        assert_eq!(Year(2023).release(Day(1)), release);
+        assert_eq!(
+            Year(2023).latest_day(release - Duration::from_secs(1)),
+            None
+        );
+        assert_eq!(Year(2023).latest_day(release), Some(Day(1)));
+        assert_eq!(
+            Year(2023).latest_day(release + Duration::from_secs(24 * 60 * 60 + 1)),
+            Some(Day(2))
+        );
+        assert_eq!(Year(2022).latest_day(release), Some(Day(25)));
+        assert_eq!(Year(2025).latest_day(release), None);
+        // 2025-12-31T00:00:00Z
+        let end = UNIX_EPOCH + Duration::from_secs(1767139200);
+        assert_eq!(Year(2025).latest_day(end), Some(Day(12)));
+    }
+
+    #[test]
+    fn test_migrate() {
+        let data_dir =
+            std::env::temp_dir().join(format!("advent2023-migrate-{}", std::process::id()));
+        std::fs::create_dir_all(&data_dir).unwrap();
+        std::fs::write(data_dir.join("day05.txt"), "input").unwrap();
+        std::fs::write(data_dir.join("day05.answers"), "1: 1").unwrap();
+        std::fs::write(data_dir.join("notes.txt"), "").unwrap();
+        // Computing the directory leaves the files alone
+        let dir = Year(2023).data_dir(&data_dir);
+        let untouched = !dir.exists() && data_dir.join("day05.txt").exists();
+        let other = Year(2022).migrate_flat_layout(&data_dir);
+        let n_moved = Year(2023).migrate_flat_layout(&data_dir);
+        let moved = std::fs::read_to_string(dir.join("day05.txt"));
+        let kept = data_dir.join("notes.txt").exists();
+        let again = Year(2023).migrate_flat_layout(&data_dir);
+        // A migration interrupted after moving some files is finished
+        std::fs::remove_dir_all(&dir).unwrap();
+        std::fs::create_dir(data_dir.join("2023.partial")).unwrap();
+        std::fs::write(data_dir.join("2023.partial/day01.txt"), "first").unwrap();
+        std::fs::write(data_dir.join("day02.txt"), "second").unwrap();
+        let resumed = Year(2023).migrate_flat_layout(&data_dir);
+        let first = std::fs::read_to_string(dir.join("day01.txt"));
+        let second = std::fs::read_to_string(dir.join("day02.txt"));
+        std::fs::remove_dir_all(&data_dir).unwrap();
+        assert_eq!(dir, data_dir.join("2023"));
+        assert!(untouched);
+        assert_eq!(other.unwrap(), 0);
+        assert_eq!(n_moved.unwrap(), 2);
+        assert_eq!(moved.unwrap(), "input");
+        assert!(kept);
+        assert_eq!(again.unwrap(), 0);
+        assert_eq!(resumed.unwrap(), 2);
+        assert_eq!(
+            (first.unwrap(), second.unwrap()),
+            ("first".into(), "second".into())
+        );
+    }
+}